@@ -0,0 +1,58 @@
+use crate::Logger;
+use std::ops::{Deref, DerefMut};
+
+/// A scope in which events are captured.
+///
+/// The event queue is cleared when the guard is created and again when it is
+/// dropped, so only events logged while the guard is alive can be observed
+/// through it. Created through [`Logger::capture_scope`].
+///
+/// # Examples
+///
+/// ```
+/// use logtest::Logger;
+///
+/// let mut logger = Logger::start();
+/// log::info!("before");
+///
+/// {
+///     let scope = logger.capture_scope();
+///     log::info!("inside");
+///     assert_eq!(scope.pop_front().unwrap().args(), "inside");
+///     assert!(scope.is_empty());
+/// }
+///
+/// log::info!("after");
+/// assert_eq!(logger.pop_front().unwrap().args(), "after");
+/// ```
+#[derive(Debug)]
+pub struct CaptureGuard<'a> {
+    logger: &'a mut Logger,
+}
+
+impl<'a> CaptureGuard<'a> {
+    pub(crate) fn new(logger: &'a mut Logger) -> Self {
+        logger.clear();
+        Self { logger }
+    }
+}
+
+impl Deref for CaptureGuard<'_> {
+    type Target = Logger;
+
+    fn deref(&self) -> &Logger {
+        self.logger
+    }
+}
+
+impl DerefMut for CaptureGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Logger {
+        self.logger
+    }
+}
+
+impl Drop for CaptureGuard<'_> {
+    fn drop(&mut self) {
+        self.logger.clear();
+    }
+}
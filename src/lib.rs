@@ -19,21 +19,39 @@
 //! single `#[test]` block that drives all log assertions. Splitting the code
 //! can be done by calling out to regular fuctions from the `#[test]` function.
 //!
+//! Starting a [`Logger`] clears the queue, so tests that each start their own
+//! logger get a clean capture as long as they don't run at the same time,
+//! for example with `--test-threads=1` or by holding a shared lock.
+//!
+//! Alternatively [`Logger::start_isolated`] gives each test thread its own
+//! queue, so multiple `#[test]` blocks can run in parallel. Events logged on
+//! other threads aren't captured by an isolated logger.
+//!
+//! # Features
+//!
+//! - `json`: adds [`Record::kv_json`] to read key-value pairs as JSON.
+//! - `regex`: adds [`Record::args_matches`], and allows passing a
+//!   `regex::Regex` to [`Logger::pop_matching`] to match message bodies.
+//! - `serde`: implements `Serialize` and `Deserialize` for [`Record`] and
+//!   [`Value`].
+//! - `tracing`: adds `TracingLayer` and `Builder::tracing` to capture
+//!   [tracing](https://docs.rs/tracing) events alongside log records.
+//!
 //! # Examples
 //!
 //! ```
 //! use logtest::Logger;
 //!
 //! // Start the logger.
-//! let mut logger = Logger::start();
+//! let logger = Logger::start();
 //!
 //! // Log some messages.
 //! log::info!("hello");
 //! log::info!("world");
 //!
 //! // The messages are now available from the logger.
-//! assert_eq!(logger.pop().unwrap().args(), "hello");
-//! assert_eq!(logger.pop().unwrap().args(), "world");
+//! assert_eq!(logger.pop_front().unwrap().args(), "hello");
+//! assert_eq!(logger.pop_front().unwrap().args(), "world");
 //! ```
 
 #![forbid(unsafe_code, future_incompatible, rust_2018_idioms)]
@@ -42,127 +60,2821 @@
 
 use lazy_static::lazy_static;
 use log::{kv, Level, LevelFilter, Metadata};
-use std::collections::{HashMap, VecDeque};
-use std::iter::Iterator;
-use std::sync::Mutex;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::{self, Iterator};
+use std::ops::{Bound, Range, RangeBounds};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant, SystemTime};
+
+pub use capture_guard::CaptureGuard;
+pub use local_logger::LocalLogger;
+pub use matcher::{Matcher, RecordMatcher};
+pub use record_builder::RecordBuilder;
+pub use records_guard::RecordsGuard;
+pub use stats::LogStats;
+#[cfg(feature = "tracing")]
+pub use tracing_layer::TracingLayer;
+pub use value::Value;
+
+mod capture_guard;
+mod local_logger;
+mod matcher;
+mod record_builder;
+mod records_guard;
+#[cfg(feature = "serde")]
+mod ser;
+mod stats;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
+mod value;
 
 /// The "payload" of a log message.
-#[derive(Debug, PartialEq, Eq)]
+///
+/// Two records are equal when their message, level, target and key-value pairs
+/// are equal. Capture metadata such as the location, timestamps and thread is
+/// not compared.
+#[derive(Debug, Clone)]
 pub struct Record {
     args: String,
+    truncated: bool,
     level: Level,
     target: String,
-    key_values: HashMap<String, String>,
+    key_values: Vec<(String, Value)>,
+    module_path: Option<String>,
+    file: Option<String>,
+    line: Option<u32>,
+    captured_at: Instant,
+    timestamp: SystemTime,
+    elapsed: Duration,
+    thread_id: ThreadId,
+    thread_name: Option<String>,
+    seq: u64,
+    error_chain: Option<Vec<String>>,
+    kv_error: Option<String>,
+    #[cfg(feature = "json")]
+    json: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl Record {
+    /// Create a `RecordBuilder` to construct an expected record.
+    pub fn builder() -> RecordBuilder {
+        RecordBuilder::default()
+    }
+
+    /// Returns `true` if the record satisfies every constraint of `matcher`.
+    pub fn matches(&self, matcher: &RecordMatcher) -> bool {
+        matcher.matches(self)
+    }
+
+    /// Returns `true` if the message body equals `args`.
+    ///
+    /// This is the same comparison as `record == "..."`, spelled out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("hello");
+    ///
+    /// let record = logger.pop_front().unwrap();
+    /// assert!(record.args_eq("hello"));
+    /// assert!(!record.args_eq("hell"));
+    /// ```
+    pub fn args_eq(&self, args: &str) -> bool {
+        self.args == args
+    }
+
+    /// Returns `true` if the message body matches `re`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    /// use regex::Regex;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("user 42 connected");
+    ///
+    /// let record = logger.pop_front().unwrap();
+    /// assert!(record.args_matches(&Regex::new(r"user \d+ connected").unwrap()));
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn args_matches(&self, re: &regex::Regex) -> bool {
+        re.is_match(&self.args)
+    }
+
     /// The message body.
     pub fn args(&self) -> &str {
         &self.args
     }
 
+    /// Returns `true` if the message body was cut short because it exceeded
+    /// [`Builder::max_args_len`].
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
     ///The verbosity level of the message.
     pub fn level(&self) -> Level {
         self.level
     }
 
+    /// The verbosity level of the message as a lowercase string, such as
+    /// `"info"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::warn!("low disk space");
+    ///
+    /// assert_eq!(logger.pop_front().unwrap().level_str(), "warn");
+    /// ```
+    pub fn level_str(&self) -> &'static str {
+        match self.level {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    /// Returns `true` if the record is at `level`.
+    pub fn is_level(&self, level: Level) -> bool {
+        self.level == level
+    }
+
+    /// Returns `true` if the record is at `level` or more severe.
+    ///
+    /// `Level` orders by verbosity, so this is `self.level() <= level`: an
+    /// error is at least a warning, but an info message isn't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::Level;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::error!("failed");
+    /// log::info!("done");
+    ///
+    /// assert!(logger.pop_front().unwrap().at_least(Level::Warn));
+    /// assert!(!logger.pop_front().unwrap().at_least(Level::Warn));
+    /// ```
+    pub fn at_least(&self, level: Level) -> bool {
+        self.level <= level
+    }
+
     /// The name of the target of the directive.
     pub fn target(&self) -> &str {
         &self.target
     }
 
     /// The structured key-value pairs associated with the message.
-    pub fn key_values(&self) -> Vec<(String, String)> {
+    ///
+    /// Pairs are returned in the order they were logged in, and duplicate keys
+    /// are preserved. String values are captured as-is, without surrounding
+    /// quotes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::{Logger, Value};
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(peer = "a", attempt = 1, peer = "b"; "connecting");
+    ///
+    /// assert_eq!(
+    ///     logger.pop_front().unwrap().key_values(),
+    ///     vec![
+    ///         ("peer".to_owned(), Value::String("a".to_owned())),
+    ///         ("attempt".to_owned(), Value::I64(1)),
+    ///         ("peer".to_owned(), Value::String("b".to_owned())),
+    ///     ]
+    /// );
+    /// ```
+    pub fn key_values(&self) -> Vec<(String, Value)> {
+        self.key_values.clone()
+    }
+
+    /// The value of the first key-value pair with the given key.
+    ///
+    /// Use [`Record::key_values`] to see every value of a duplicated key.
+    pub fn key_value(&self, key: &str) -> Option<&Value> {
         self.key_values
             .iter()
-            .map(|(k, v)| (k.to_owned(), v.to_owned()))
-            .collect()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Parse the value of the first key-value pair with the given key.
+    ///
+    /// Returns `None` if the key is missing or the value fails to parse. Use
+    /// [`Record::get_parsed`] to tell the two apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(elapsed_ms = 12; "done");
+    ///
+    /// let record = logger.pop_front().unwrap();
+    /// assert_eq!(record.get::<u64>("elapsed_ms"), Some(12));
+    /// assert_eq!(record.get::<u64>("missing"), None);
+    /// ```
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get_parsed(key).ok().flatten()
+    }
+
+    /// Parse the value of the first key-value pair with the given key,
+    /// returning an error if the value fails to parse.
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Result<Option<T>, T::Err> {
+        self.key_value(key)
+            .map(|value| value.to_string().parse())
+            .transpose()
+    }
+
+    /// The module path of the message, if available.
+    ///
+    /// Unlike [`Record::target`] this can't be overridden by the log macros,
+    /// so it always points at the module that emitted the message.
+    pub fn module_path(&self) -> Option<&str> {
+        self.module_path.as_deref()
+    }
+
+    /// The source file containing the message, if available.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The line containing the message, if available.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// The moment the message was captured by the logger.
+    pub fn captured_at(&self) -> Instant {
+        self.captured_at
+    }
+
+    /// The wall-clock time at which the message was logged.
+    ///
+    /// Timestamps never decrease in queue order, even if the system clock
+    /// moves backwards between two messages.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// The time between the logger being started and the message being
+    /// captured.
+    ///
+    /// Elapsed times never decrease in queue order, so subtracting them gives
+    /// the time between two messages. Starting the logger again resets the
+    /// starting point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    /// use std::time::Duration;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("retrying");
+    /// log::info!("giving up");
+    ///
+    /// let first = logger.pop_front().unwrap();
+    /// let last = logger.pop_front().unwrap();
+    /// assert!(last.elapsed() - first.elapsed() < Duration::from_secs(1));
+    /// ```
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The id of the thread the message was logged from.
+    pub fn thread_id(&self) -> ThreadId {
+        self.thread_id
+    }
+
+    /// The name of the thread the message was logged from, if it was named.
+    pub fn thread_name(&self) -> Option<&str> {
+        self.thread_name.as_deref()
+    }
+
+    /// The sequence number of the message.
+    ///
+    /// Sequence numbers are unique and strictly increase in the order
+    /// messages enter the queue, across all threads. Captured records start
+    /// at 1; records that were never captured, such as those built with
+    /// [`Record::builder`], have sequence number 0.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// The structured key-value pairs associated with the message, as JSON.
+    ///
+    /// Numbers and booleans are kept as JSON numbers and booleans, and strings
+    /// don't carry extra quotes. Values captured with `serde` keep their
+    /// nested structure. If a key is logged more than once, the first value
+    /// is used, like [`Record::key_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(retries = 3, ok = true; "done");
+    ///
+    /// let record = logger.pop_front().unwrap();
+    /// assert_eq!(record.kv_json()["retries"], 3);
+    /// assert_eq!(record.kv_json()["ok"], true);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn kv_json(&self) -> serde_json::Map<String, serde_json::Value> {
+        match &self.json {
+            Some(json) => json.clone(),
+            None => {
+                let mut json = serde_json::Map::new();
+                for (key, value) in &self.key_values {
+                    json.entry(key.clone()).or_insert_with(|| value.to_json());
+                }
+                json
+            }
+        }
+    }
+
+    /// The messages of the first error logged as a key-value pair, followed by
+    /// the messages of each of its sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// let err = std::io::Error::new(std::io::ErrorKind::Other, "oh no");
+    /// log::error!(err:err = err; "request failed");
+    ///
+    /// let record = logger.pop_front().unwrap();
+    /// assert_eq!(record.error_chain(), Some(&["oh no".to_owned()][..]));
+    /// ```
+    pub fn error_chain(&self) -> Option<&[String]> {
+        self.error_chain.as_deref()
+    }
+
+    /// The error returned while visiting the key-value pairs, if any.
+    ///
+    /// When visiting fails the record is still captured, with the key-value
+    /// pairs that were visited before the error.
+    pub fn kv_error(&self) -> Option<&str> {
+        self.kv_error.as_deref()
+    }
+}
+
+/// Formats the record as `[LEVEL target] args {key=value ...}`.
+///
+/// Key-value pairs are written in the order they were logged in, with string
+/// values quoted. The braces are omitted when there are no pairs.
+///
+/// # Examples
+///
+/// ```
+/// use logtest::Logger;
+///
+/// let logger = Logger::start();
+/// log::info!(target: "my_crate::db", attempt = 1, host = "localhost"; "connected");
+///
+/// let record = logger.pop_front().unwrap();
+/// assert_eq!(
+///     record.to_string(),
+///     r#"[INFO my_crate::db] connected {attempt=1 host="localhost"}"#
+/// );
+/// ```
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} {}] {}", self.level, self.target, self.args)?;
+        for (i, (key, value)) in self.key_values.iter().enumerate() {
+            f.write_str(if i == 0 { " {" } else { " " })?;
+            match value {
+                Value::String(v) => write!(f, "{}={:?}", key, v)?,
+                value => write!(f, "{}={}", key, value)?,
+            }
+        }
+        if !self.key_values.is_empty() {
+            f.write_str("}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Record {
+    fn eq(&self, other: &Self) -> bool {
+        self.args == other.args
+            && self.level == other.level
+            && self.target == other.target
+            && self.key_values == other.key_values
+    }
+}
+
+/// Compares only the message body, ignoring every other field.
+///
+/// # Examples
+///
+/// ```
+/// use logtest::Logger;
+///
+/// let logger = Logger::start();
+/// log::info!("hello");
+/// assert_eq!(logger.pop_front().unwrap(), "hello");
+/// ```
+impl PartialEq<&str> for Record {
+    fn eq(&self, other: &&str) -> bool {
+        self.args == *other
+    }
+}
+
+/// Compares only the message body, ignoring every other field.
+impl PartialEq<Record> for &str {
+    fn eq(&self, other: &Record) -> bool {
+        other == self
+    }
+}
+
+/// Compares only the level and message body, ignoring every other field.
+///
+/// # Examples
+///
+/// ```
+/// use log::Level;
+/// use logtest::Logger;
+///
+/// let logger = Logger::start();
+/// log::warn!("disk nearly full");
+/// assert_eq!(logger.pop_front().unwrap(), (Level::Warn, "disk nearly full"));
+/// ```
+impl PartialEq<(Level, &str)> for Record {
+    fn eq(&self, (level, args): &(Level, &str)) -> bool {
+        self.level == *level && self.args == *args
+    }
+}
+
+/// Compares only the level and message body, ignoring every other field.
+impl PartialEq<Record> for (Level, &str) {
+    fn eq(&self, other: &Record) -> bool {
+        other == self
+    }
+}
+
+/// A queue of captured events.
+#[derive(Debug, Default)]
+struct Queue {
+    events: Mutex<VecDeque<Record>>,
+    /// Signaled whenever an event is pushed onto `events`.
+    pushed: Condvar,
+    /// The number of events discarded because the queue was full.
+    dropped: AtomicUsize,
+    /// Whether capturing was stopped through `Logger::finish`.
+    finished: AtomicBool,
+    /// The events removed from `events`, if history is enabled.
+    history: Mutex<Option<History>>,
+    /// When the logger reading this queue was started, to compute
+    /// `Record::elapsed`.
+    started_at: Mutex<Option<Instant>>,
+}
+
+impl Queue {
+    /// Copy `records` into the history, if it's enabled.
+    fn remember<'r>(&self, records: impl IntoIterator<Item = &'r Record>) {
+        if let Some(history) = &mut *self.history.lock().unwrap_or_else(PoisonError::into_inner) {
+            for record in records {
+                history.push(record.clone());
+            }
+        }
+    }
+}
+
+/// The events removed from a queue.
+///
+/// See [`Builder::history`].
+#[derive(Debug, Default)]
+struct History {
+    records: VecDeque<Record>,
+    /// The max number of records to keep, dropping the oldest first.
+    limit: Option<usize>,
+}
+
+impl History {
+    fn push(&mut self, record: Record) {
+        if let Some(limit) = self.limit {
+            if limit == 0 {
+                return;
+            }
+            if self.records.len() >= limit {
+                self.records.pop_front();
+            }
+        }
+        self.records.push_back(record);
     }
 }
 
 lazy_static! {
-    /// The internal queue of events.
-    static ref EVENTS: Mutex<VecDeque<Record>> = Mutex::new(VecDeque::new());
+    /// The global queue of events.
+    static ref EVENTS: Arc<Queue> = Arc::default();
+}
+
+thread_local! {
+    /// The queue of the isolated logger started on this thread, if any.
+    static LOCAL: RefCell<Option<Arc<Queue>>> = const { RefCell::new(None) };
+}
+
+/// Whether `LoggerInternal` has been installed as the global logger.
+static INSTALLED: Mutex<bool> = Mutex::new(false);
+
+/// Whether `TracingLayer` has been installed as the global subscriber.
+#[cfg(feature = "tracing")]
+static TRACING_INSTALLED: Mutex<bool> = Mutex::new(false);
+
+/// The current capture configuration.
+static CONFIG: Mutex<Config> = Mutex::new(Config::new());
+
+/// The number of live `Logger` handles.
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// The sequence number of the next captured event.
+///
+/// This starts at 1, so 0 is left for records that were never captured.
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// Which events are captured.
+#[derive(Debug)]
+struct Config {
+    level: LevelFilter,
+    targets: Vec<String>,
+    excluded_targets: Vec<String>,
+    max_args_len: Option<usize>,
+    capacity: Option<usize>,
+    overflow: OverflowPolicy,
+    sink: Option<Sink>,
+}
+
+impl Config {
+    const fn new() -> Self {
+        Self {
+            level: LevelFilter::Trace,
+            targets: Vec::new(),
+            excluded_targets: Vec::new(),
+            max_args_len: None,
+            capacity: None,
+            overflow: OverflowPolicy::DropOldest,
+            sink: None,
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+            && (self.targets.is_empty()
+                || self
+                    .targets
+                    .iter()
+                    .any(|prefix| metadata.target().starts_with(prefix.as_str())))
+            && !self
+                .excluded_targets
+                .iter()
+                .any(|prefix| metadata.target().starts_with(prefix.as_str()))
+    }
+}
+
+/// A callback invoked for every captured event.
+#[derive(Clone)]
+struct Sink(Arc<dyn Fn(&Record) + Send + Sync>);
+
+impl fmt::Debug for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sink").finish_non_exhaustive()
+    }
 }
 
 /// A log key-value visitor.
 struct Visitor {
-    pairs: HashMap<String, String>,
+    pairs: Vec<(String, Value)>,
+    error_chain: Option<Vec<String>>,
+    #[cfg(feature = "json")]
+    json: serde_json::Map<String, serde_json::Value>,
 }
 
 impl<'kvs> kv::Visitor<'kvs> for Visitor {
     fn visit_pair(&mut self, key: kv::Key<'kvs>, val: kv::Value<'kvs>) -> Result<(), kv::Error> {
-        self.pairs.insert(format!("{}", key), val.to_string());
+        if self.error_chain.is_none() {
+            let mut chain = Vec::new();
+            let mut next = val.to_borrowed_error();
+            while let Some(err) = next {
+                chain.push(err.to_string());
+                next = err.source();
+            }
+            if !chain.is_empty() {
+                self.error_chain = Some(chain);
+            }
+        }
+        #[cfg(feature = "json")]
+        self.json.entry(key.to_string()).or_insert_with(|| {
+            serde_json::to_value(&val).unwrap_or_else(|_| val.to_string().into())
+        });
+        self.pairs.push((format!("{}", key), Value::capture(&val)));
         Ok(())
     }
 }
 
+impl Record {
+    /// Capture `record`.
+    ///
+    /// The fields that depend on the position in the queue are assigned by
+    /// `Record::stamp`.
+    fn capture(record: &log::Record<'_>, max_args_len: Option<usize>) -> Self {
+        let mut args = format!("{}", record.args());
+        let truncated = truncate_args(&mut args, max_args_len);
+        let mut visitor = Visitor {
+            pairs: Vec::new(),
+            error_chain: None,
+            #[cfg(feature = "json")]
+            json: serde_json::Map::new(),
+        };
+        let kv_error = record
+            .key_values()
+            .visit(&mut visitor)
+            .err()
+            .map(|err| err.to_string());
+        let mut captured = Record::from_parts(
+            args,
+            record.level(),
+            record.target().to_owned(),
+            visitor.pairs,
+        );
+        captured.truncated = truncated;
+        captured.module_path = record.module_path().map(ToOwned::to_owned);
+        captured.file = record.file().map(ToOwned::to_owned);
+        captured.line = record.line();
+        captured.error_chain = visitor.error_chain;
+        captured.kv_error = kv_error;
+        #[cfg(feature = "json")]
+        {
+            captured.json = Some(visitor.json);
+        }
+        captured
+    }
+
+    /// Create a record from its message, level, target and key-value pairs.
+    ///
+    /// The source location and other optional fields are left empty, capture
+    /// metadata is taken from the current moment and thread, and the
+    /// sequence number is 0 until the record is stamped.
+    fn from_parts(
+        args: String,
+        level: Level,
+        target: String,
+        key_values: Vec<(String, Value)>,
+    ) -> Self {
+        let thread = thread::current();
+        Record {
+            args,
+            truncated: false,
+            level,
+            target,
+            key_values,
+            module_path: None,
+            file: None,
+            line: None,
+            captured_at: Instant::now(),
+            timestamp: SystemTime::now(),
+            elapsed: Duration::ZERO,
+            thread_id: thread.id(),
+            thread_name: thread.name().map(ToOwned::to_owned),
+            seq: 0,
+            error_chain: None,
+            kv_error: None,
+            #[cfg(feature = "json")]
+            json: None,
+        }
+    }
+
+    /// Assign the fields that depend on the position in `events`, right
+    /// before pushing onto it.
+    ///
+    /// This must be called while holding the lock on `events`, so the
+    /// sequence number and timestamps match the queue order.
+    fn stamp(&mut self, events: &VecDeque<Record>, started_at: Option<Instant>) {
+        if let Some(last) = events.back() {
+            if last.timestamp > self.timestamp {
+                self.timestamp = last.timestamp;
+            }
+        }
+        self.captured_at = Instant::now();
+        self.elapsed = started_at.map_or(Duration::ZERO, |start| {
+            self.captured_at.saturating_duration_since(start)
+        });
+        self.seq = NEXT_SEQ.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Truncate `args` to at most `max_args_len` bytes on a char boundary,
+/// returning whether anything was cut off.
+fn truncate_args(args: &mut String, max_args_len: Option<usize>) -> bool {
+    match max_args_len {
+        Some(max) if args.len() > max => {
+            let end = (0..=max).rev().find(|&i| args.is_char_boundary(i));
+            args.truncate(end.unwrap_or(0));
+            true
+        }
+        _ => false,
+    }
+}
+
 /// The logger impl. This is for internal use only.
 #[derive(Debug)]
 struct LoggerInternal;
 
+/// The settings needed to capture a single event, copied out of `CONFIG` so
+/// the lock isn't held while the event is pushed.
+struct Capture {
+    max_args_len: Option<usize>,
+    capacity: Option<usize>,
+    overflow: OverflowPolicy,
+    sink: Option<Sink>,
+}
+
+impl Capture {
+    /// Load the settings, or return `None` if events with `metadata` aren't
+    /// captured.
+    fn load(metadata: &Metadata<'_>) -> Option<Self> {
+        let config = CONFIG.lock().unwrap_or_else(PoisonError::into_inner);
+        if !config.enabled(metadata) {
+            return None;
+        }
+        Some(Self {
+            max_args_len: config.max_args_len,
+            capacity: config.capacity,
+            overflow: config.overflow,
+            sink: config.sink.clone(),
+        })
+    }
+
+    /// Push `captured` onto the queue of the current thread.
+    fn push(self, mut captured: Record) {
+        // Events from threads without an isolated logger go to the global queue.
+        let queue = LOCAL
+            .try_with(|local| local.borrow().clone())
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| EVENTS.clone());
+        records_guard::assert_not_held();
+        let mut events = queue.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if queue.finished.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(capacity) = self.capacity {
+            if events.len() >= capacity {
+                queue.dropped.fetch_add(1, Ordering::Relaxed);
+                if capacity == 0 || self.overflow == OverflowPolicy::DropNewest {
+                    return;
+                }
+                events.pop_front();
+            }
+        }
+        let started_at = *queue
+            .started_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        captured.stamp(&events, started_at);
+        // Call the sink outside of the lock so it may log or read the queue.
+        let copy = self.sink.as_ref().map(|_| captured.clone());
+        events.push_back(captured);
+        drop(events);
+        queue.pushed.notify_all();
+        if let (Some(Sink(sink)), Some(copy)) = (self.sink, copy) {
+            sink(&copy);
+        }
+    }
+}
+
 impl log::Log for LoggerInternal {
-    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
-        true
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        CONFIG
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .enabled(metadata)
     }
 
     fn log(&self, record: &log::Record<'_>) {
-        if self.enabled(record.metadata()) {
-            let mut visitor = Visitor {
-                pairs: HashMap::new(),
-            };
-            record
-                .key_values()
-                .visit(&mut visitor)
-                .expect("could not visit kv pairs");
-            EVENTS.lock().unwrap().push_back(Record {
-                args: format!("{}", record.args()),
-                level: record.level(),
-                target: record.target().to_owned(),
-                key_values: visitor.pairs,
-            });
+        if let Some(capture) = Capture::load(record.metadata()) {
+            let captured = Record::capture(record, capture.max_args_len);
+            capture.push(captured);
         }
     }
     fn flush(&self) {}
 }
 
 /// The test logger.
+///
+/// The event queue is synchronized internally, so most methods take `&self`
+/// and a `&Logger` can be shared between threads and helper functions.
+///
+/// Dropping the `Logger` restores the global max level to what it was before
+/// the logger was started. When the logger is started more than once, the
+/// handles should be dropped in reverse order.
 #[derive(Debug)]
-pub struct Logger;
+pub struct Logger {
+    /// The max level to restore on drop.
+    prior_level: Option<LevelFilter>,
+    /// The queue events are read from.
+    queue: Arc<Queue>,
+    /// Whether this handle started an isolated logger on its thread.
+    isolated: bool,
+}
 
 impl Logger {
     /// Create a new instance of `Logger` and start listening for events.
+    ///
+    /// Calling this more than once clears the queue and returns a new handle
+    /// to it, so every `#[test]` can start its own logger. Use
+    /// [`Logger::clone`] to get another handle without clearing the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a different global logger has already been installed. Use
+    /// [`Logger::try_start`] to handle this case.
     pub fn start() -> Self {
-        log::set_logger(&LoggerInternal).unwrap();
-        log::set_max_level(LevelFilter::Trace);
-        Self {}
+        Self::builder().start()
+    }
+
+    /// Create a new instance of `Logger` and start listening for events,
+    /// returning an error if a different global logger has already been
+    /// installed.
+    ///
+    /// Calling this more than once clears the queue and returns a new handle
+    /// to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let first = Logger::try_start().unwrap();
+    /// log::info!("setup");
+    ///
+    /// let second = Logger::try_start().unwrap();
+    /// assert!(first.is_empty());
+    /// log::info!("test");
+    /// assert_eq!(second.pop_front().unwrap().args(), "test");
+    /// ```
+    pub fn try_start() -> Result<Self, StartError> {
+        Self::builder().try_start()
+    }
+
+    /// Create a new instance of `Logger` and start listening for events at
+    /// `level` or above.
+    ///
+    /// Events below `level` are never captured. The global max level is set
+    /// to `level` as well, so the `log` macros skip those events entirely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::LevelFilter;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start_with_level(LevelFilter::Warn);
+    /// log::info!("ignored");
+    /// log::warn!("captured");
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn start_with_level(level: LevelFilter) -> Self {
+        Self::builder().level(level).start()
+    }
+
+    /// Create a new instance of `Logger` and start listening for events whose
+    /// target starts with `prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start_with_target("my_crate");
+    /// log::info!(target: "hyper", "ignored");
+    /// log::info!(target: "my_crate::db", "captured");
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn start_with_target(prefix: impl Into<String>) -> Self {
+        Self::builder().target(prefix).start()
+    }
+
+    /// Create a new instance of `Logger` and start listening for events whose
+    /// target doesn't start with any of `prefixes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start_excluding(&["hyper", "tokio"]);
+    /// log::info!(target: "hyper::client", "ignored");
+    /// log::info!(target: "my_crate::db", "captured");
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn start_excluding(prefixes: &[&str]) -> Self {
+        prefixes
+            .iter()
+            .fold(Self::builder(), |builder, prefix| {
+                builder.exclude_target(*prefix)
+            })
+            .start()
+    }
+
+    /// Create a new instance of `Logger` that also calls `sink` with every
+    /// captured event.
+    ///
+    /// See [`Builder::sink`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let seen = Arc::new(AtomicUsize::new(0));
+    /// let counter = seen.clone();
+    /// let logger = Logger::start_with_sink(move |_| {
+    ///     counter.fetch_add(1, Ordering::SeqCst);
+    /// });
+    /// log::info!("hello");
+    ///
+    /// assert_eq!(seen.load(Ordering::SeqCst), 1);
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn start_with_sink<F>(sink: F) -> Self
+    where
+        F: Fn(&Record) + Send + Sync + 'static,
+    {
+        Self::builder().sink(sink).start()
+    }
+
+    /// Create a new instance of `Logger` that keeps at most `capacity` events,
+    /// dropping the oldest event when the queue is full.
+    ///
+    /// Use [`Builder::overflow`] to drop new events instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start_with_capacity(2);
+    /// log::info!("one");
+    /// log::info!("two");
+    /// log::info!("three");
+    ///
+    /// assert_eq!(logger.dropped_count(), 1);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "two");
+    /// ```
+    pub fn start_with_capacity(capacity: usize) -> Self {
+        Self::builder().capacity(capacity).start()
+    }
+
+    /// Create a `Builder` to configure which events are captured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::LevelFilter;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::builder()
+    ///     .level(LevelFilter::Info)
+    ///     .target("my_crate")
+    ///     .target("my_other_crate")
+    ///     .start();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder {
+            config: Config::new(),
+            isolated: false,
+            preserve_level: false,
+            history: None,
+            #[cfg(feature = "tracing")]
+            tracing: false,
+        }
+    }
+
+    /// Create a new instance of `Logger` that leaves the global max level
+    /// untouched.
+    ///
+    /// See [`Builder::preserve_level`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::LevelFilter;
+    /// use logtest::Logger;
+    ///
+    /// log::set_max_level(LevelFilter::Warn);
+    /// let logger = Logger::start_preserving_level();
+    /// log::info!("filtered out by the max level");
+    /// log::warn!("captured");
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn start_preserving_level() -> Self {
+        Self::builder().preserve_level().start()
+    }
+
+    /// Create a new instance of `Logger` that only captures events logged on
+    /// the current thread.
+    ///
+    /// Because every `#[test]` runs on its own thread, this allows a test file
+    /// to contain multiple `#[test]` blocks that log in parallel. See
+    /// [`Builder::isolated`] for the limitations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start_isolated();
+    /// log::info!("hello");
+    /// assert_eq!(logger.pop_front().unwrap().args(), "hello");
+    /// ```
+    pub fn start_isolated() -> Self {
+        Self::builder().isolated().start()
+    }
+
+    /// Install the global logger, and replace the current configuration.
+    fn install(builder: Builder) -> Result<Self, StartError> {
+        let Builder {
+            config,
+            isolated,
+            preserve_level,
+            history,
+            #[cfg(feature = "tracing")]
+            tracing,
+        } = builder;
+        let mut installed = INSTALLED.lock().unwrap_or_else(PoisonError::into_inner);
+        if !*installed {
+            log::set_logger(&LoggerInternal).map_err(StartErrorKind::Log)?;
+            *installed = true;
+        }
+        #[cfg(feature = "tracing")]
+        if tracing {
+            let mut installed = TRACING_INSTALLED
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if !*installed {
+                use tracing_subscriber::layer::SubscriberExt;
+                let subscriber = tracing_subscriber::registry().with(TracingLayer);
+                tracing::subscriber::set_global_default(subscriber)
+                    .map_err(StartErrorKind::Tracing)?;
+                *installed = true;
+            }
+        }
+        let started_at = Instant::now();
+        let level = config.level;
+        *CONFIG.lock().unwrap_or_else(PoisonError::into_inner) = config;
+        let prior_level = if preserve_level {
+            None
+        } else {
+            let prior_level = log::max_level();
+            log::set_max_level(level);
+            Some(prior_level)
+        };
+        ACTIVE.fetch_add(1, Ordering::Relaxed);
+        if !isolated {
+            EVENTS.dropped.store(0, Ordering::Relaxed);
+            EVENTS.finished.store(false, Ordering::Relaxed);
+            *EVENTS
+                .history
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = history;
+            records_guard::assert_not_held();
+            let mut events = EVENTS.events.lock().unwrap_or_else(PoisonError::into_inner);
+            events.clear();
+            *EVENTS
+                .started_at
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(started_at);
+            drop(events);
+            return Ok(Self {
+                prior_level,
+                queue: EVENTS.clone(),
+                isolated: false,
+            });
+        }
+        let queue = Arc::new(Queue {
+            history: Mutex::new(history),
+            started_at: Mutex::new(Some(started_at)),
+            ..Queue::default()
+        });
+        LOCAL.with(|local| *local.borrow_mut() = Some(queue.clone()));
+        // Isolated loggers may be dropped in any order, so restoring the max
+        // level could stop capturing for loggers on other threads.
+        Ok(Self {
+            prior_level: None,
+            queue,
+            isolated: true,
+        })
+    }
+
+    /// Lock the event queue.
+    fn events(&self) -> MutexGuard<'_, VecDeque<Record>> {
+        records_guard::assert_not_held();
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wait until an event is pushed onto the queue, or return `None` once
+    /// `deadline` has passed. Without a deadline this waits indefinitely.
+    fn wait_pushed<'a>(
+        &'a self,
+        events: MutexGuard<'a, VecDeque<Record>>,
+        deadline: Option<Instant>,
+    ) -> Option<MutexGuard<'a, VecDeque<Record>>> {
+        let events = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining == Duration::ZERO {
+                    return None;
+                }
+                self.queue
+                    .pushed
+                    .wait_timeout(events, remaining)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            }
+            None => self
+                .queue
+                .pushed
+                .wait(events)
+                .unwrap_or_else(PoisonError::into_inner),
+        };
+        Some(events)
+    }
+
+    /// Lock the event queue, to visit events by reference without copying
+    /// them.
+    ///
+    /// No events are captured while the returned guard is alive, and logging
+    /// on the same thread panics. See [`RecordsGuard`] for an example.
+    pub fn lock(&self) -> RecordsGuard<'_> {
+        RecordsGuard::new(self.events())
+    }
+
+    /// Pop an event from the front of the event queue.
+    #[must_use]
+    pub fn pop_front(&self) -> Option<Record> {
+        let record = self.events().pop_front();
+        self.queue.remember(&record);
+        record
     }
 
     /// Pop an event from the front of the event queue.
     #[must_use]
-    pub fn pop(&mut self) -> Option<Record> {
-        EVENTS.lock().unwrap().pop_front()
+    #[deprecated(since = "3.0.0", note = "use `Logger::pop_front` instead")]
+    pub fn pop(&self) -> Option<Record> {
+        self.pop_front()
+    }
+
+    /// Pop an event from the back of the event queue.
+    #[must_use]
+    pub fn pop_back(&self) -> Option<Record> {
+        let record = self.events().pop_back();
+        self.queue.remember(&record);
+        record
+    }
+
+    /// Pop up to `n` events from the front of the event queue, in order.
+    ///
+    /// Returns fewer than `n` events if the queue is shorter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("one");
+    /// log::info!("two");
+    /// log::info!("three");
+    ///
+    /// let records = logger.pop_n(2);
+    /// assert_eq!(records[0].args(), "one");
+    /// assert_eq!(records[1].args(), "two");
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn pop_n(&self, n: usize) -> Vec<Record> {
+        let mut events = self.events();
+        let n = n.min(events.len());
+        let records: Vec<_> = events.drain(..n).collect();
+        self.queue.remember(&records);
+        records
+    }
+
+    /// Pop every event from the event queue, in order.
+    ///
+    /// This is the same as `drain(..)`.
+    #[must_use]
+    pub fn pop_all(&self) -> Vec<Record> {
+        self.drain(..)
+    }
+
+    /// Pop an event from the front of the event queue, but only if it matches
+    /// `pred`.
+    ///
+    /// The queue is left untouched if the front event doesn't match, or if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::Level;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("connected");
+    ///
+    /// assert!(logger.pop_if(|r| r.level() == Level::Warn).is_none());
+    /// assert!(logger.pop_if(|r| r.level() == Level::Info).is_some());
+    /// ```
+    #[must_use]
+    pub fn pop_if<F>(&self, pred: F) -> Option<Record>
+    where
+        F: FnOnce(&Record) -> bool,
+    {
+        let mut events = self.events();
+        if !pred(events.front()?) {
+            return None;
+        }
+        let record = events.pop_front();
+        self.queue.remember(&record);
+        record
+    }
+
+    /// Return a copy of the event at the front of the event queue, without
+    /// removing it.
+    pub fn peek(&self) -> Option<Record> {
+        self.events().front().cloned()
+    }
+
+    /// Return a copy of the event at the back of the event queue, without
+    /// removing it.
+    ///
+    /// This is the most recently logged event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// for i in 0..3 {
+    ///     log::info!("starting {}", i);
+    ///     log::info!("finished {}", i);
+    ///     assert_eq!(logger.peek_back().unwrap().args(), format!("finished {}", i));
+    ///     logger.clear();
+    /// }
+    /// ```
+    pub fn peek_back(&self) -> Option<Record> {
+        self.events().back().cloned()
+    }
+
+    /// Call `f` with the event at the front of the event queue, without
+    /// copying or removing it.
+    ///
+    /// The queue is locked while `f` runs, so `f` must not log.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::Level;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::warn!("careful");
+    ///
+    /// let level = logger.peek_with(|record| record.map(|r| r.level()));
+    /// assert_eq!(level, Some(Level::Warn));
+    /// ```
+    pub fn peek_with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(Option<&Record>) -> R,
+    {
+        f(self.events().front())
+    }
+
+    /// Return a copy of every event in the event queue, without removing them.
+    ///
+    /// The copy reflects the queue at the moment of the call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("hello");
+    ///
+    /// let records = logger.records();
+    /// assert_eq!(records[0].args(), "hello");
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn records(&self) -> Vec<Record> {
+        self.events().iter().cloned().collect()
+    }
+
+    /// Mark the current position in the event queue.
+    ///
+    /// The checkpoint holds the sequence number the next captured event will
+    /// get, which is always at least 1. Pass it to [`Logger::since`] to read
+    /// the events logged after it.
+    pub fn checkpoint(&self) -> Checkpoint {
+        // Hold the lock so no event is being pushed while the position is read.
+        let _events = self.events();
+        Checkpoint(NEXT_SEQ.load(Ordering::Relaxed))
+    }
+
+    /// Copy the events in the event queue that were logged after
+    /// `checkpoint`, without removing them.
+    ///
+    /// Checkpoints use sequence numbers rather than indexes, so events popped
+    /// in the meantime don't shift them. Only captured events are compared,
+    /// since every event in the queue has a non-zero [`Record::seq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("setup");
+    /// let checkpoint = logger.checkpoint();
+    /// log::info!("test");
+    ///
+    /// let records = logger.since(&checkpoint);
+    /// assert_eq!(records.len(), 1);
+    /// assert_eq!(records[0].args(), "test");
+    /// ```
+    pub fn since(&self, checkpoint: &Checkpoint) -> Vec<Record> {
+        self.events()
+            .iter()
+            .filter(|r| r.seq >= checkpoint.0)
+            .cloned()
+            .collect()
+    }
+
+    /// Iterate over a copy of the event queue, without removing any events.
+    ///
+    /// Unlike iterating over the `Logger` itself, this can be done multiple
+    /// times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("hello");
+    /// log::info!("world");
+    ///
+    /// assert_eq!(logger.iter().count(), 2);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "hello");
+    /// ```
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Record> + ExactSizeIterator {
+        self.records().into_iter()
+    }
+
+    /// Remove and yield the events at `level`, in order.
+    ///
+    /// Events at other levels stay queued.
+    pub fn at_level(&self, level: Level) -> impl Iterator<Item = Record> + '_ {
+        iter::from_fn(move || self.remove_first(|r| r.is_level(level)))
+    }
+
+    /// Remove and yield the events at `level` or more severe, in order.
+    ///
+    /// Less severe events stay queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::Level;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("starting");
+    /// log::warn!("slow");
+    /// log::error!("failed");
+    ///
+    /// assert_eq!(logger.at_least(Level::Warn).count(), 2);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "starting");
+    /// ```
+    pub fn at_least(&self, level: Level) -> impl Iterator<Item = Record> + '_ {
+        iter::from_fn(move || self.remove_first(|r| r.at_least(level)))
+    }
+
+    /// Remove and yield the events whose target is `prefix` or one of its
+    /// submodules, in order.
+    ///
+    /// Matching respects module path boundaries, so `"my_crate"` matches
+    /// `"my_crate::db"` but not `"my_crate_extras"`. Other events stay queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(target: "my_crate::db", "connected");
+    /// log::info!(target: "my_crate_extras", "loaded");
+    ///
+    /// assert_eq!(logger.from_target("my_crate").count(), 1);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "loaded");
+    /// ```
+    pub fn from_target<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = Record> + 'a {
+        iter::from_fn(move || {
+            self.remove_first(|r| {
+                r.target == prefix
+                    || r.target
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+        })
+    }
+
+    /// Remove and yield the errors, in order.
+    ///
+    /// Events at other levels stay queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("starting");
+    /// log::error!("failed");
+    ///
+    /// assert_eq!(logger.errors().count(), 1);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "starting");
+    /// ```
+    pub fn errors(&self) -> impl Iterator<Item = Record> + '_ {
+        self.at_level(Level::Error)
+    }
+
+    /// Remove and yield the warnings, in order.
+    ///
+    /// Events at other levels, including errors, stay queued.
+    pub fn warnings(&self) -> impl Iterator<Item = Record> + '_ {
+        self.at_level(Level::Warn)
+    }
+
+    /// Remove and return the first event with a key-value pair matching `key`
+    /// and `value`.
+    ///
+    /// Values are compared using their `Display` output. The order of the
+    /// remaining events is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(request_id = 1; "started");
+    /// log::info!(request_id = 2; "started");
+    ///
+    /// let record = logger.find_by_kv("request_id", "2").unwrap();
+    /// assert_eq!(record.get::<u64>("request_id"), Some(2));
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn find_by_kv(&self, key: &str, value: &str) -> Option<Record> {
+        self.remove_first(|record| record.key_value(key).is_some_and(|v| *v == *value))
+    }
+
+    /// Remove and return the first event that matches `pred`.
+    ///
+    /// The order of the remaining events is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::Level;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("polling");
+    /// log::warn!("slow response");
+    /// log::info!("polling");
+    ///
+    /// let record = logger.remove_first(|r| r.level() == Level::Warn).unwrap();
+    /// assert_eq!(record.args(), "slow response");
+    /// assert_eq!(logger.len(), 2);
+    /// ```
+    pub fn remove_first<F>(&self, pred: F) -> Option<Record>
+    where
+        F: Fn(&Record) -> bool,
+    {
+        let mut events = self.events();
+        let index = events.iter().position(pred)?;
+        let record = events.remove(index);
+        self.queue.remember(&record);
+        record
+    }
+
+    /// Returns `true` if any event in the event queue matches `pred`.
+    ///
+    /// No events are removed.
+    pub fn contains<F>(&self, pred: F) -> bool
+    where
+        F: Fn(&Record) -> bool,
+    {
+        self.events().iter().any(pred)
+    }
+
+    /// Returns `true` if the message body of any event in the event queue
+    /// contains `needle`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("hello world");
+    ///
+    /// assert!(logger.contains_args("world"));
+    /// assert!(!logger.contains_args("goodbye"));
+    /// ```
+    pub fn contains_args(&self, needle: &str) -> bool {
+        self.contains(|r| r.args().contains(needle))
+    }
+
+    /// Returns `true` if any event in the event queue matches `matcher`.
+    ///
+    /// No events are removed. This takes `&mut self` so that it takes
+    /// precedence over `Iterator::any` on `&mut Logger`.
+    pub fn any<M>(&mut self, matcher: &M) -> bool
+    where
+        M: Matcher + ?Sized,
+    {
+        self.events().iter().any(|r| matcher.matches(r))
+    }
+
+    /// Returns `true` if every event in the event queue matches `matcher`.
+    ///
+    /// No events are removed, and this is `true` for an empty queue.
+    pub fn all<M>(&mut self, matcher: &M) -> bool
+    where
+        M: Matcher + ?Sized,
+    {
+        self.events().iter().all(|r| matcher.matches(r))
+    }
+
+    /// Returns the index of the first event in the event queue that matches
+    /// `pred`.
+    ///
+    /// No events are removed. This takes `&mut self` so that it takes
+    /// precedence over `Iterator::position` on `&mut Logger`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let mut logger = Logger::start();
+    /// log::info!("opened");
+    /// log::info!("closed");
+    ///
+    /// let opened = logger.position(|r| r.args() == "opened").unwrap();
+    /// let closed = logger.position(|r| r.args() == "closed").unwrap();
+    /// assert!(opened < closed);
+    /// ```
+    pub fn position<F>(&mut self, pred: F) -> Option<usize>
+    where
+        F: Fn(&Record) -> bool,
+    {
+        self.events().iter().position(pred)
+    }
+
+    /// Remove and return the first event matching `matcher`.
+    ///
+    /// The order of the remaining events is preserved. With the `regex`
+    /// feature, a [`regex::Regex`] matches the message body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::{Logger, RecordMatcher};
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("listening");
+    /// log::info!("user 42 connected");
+    ///
+    /// let matcher = RecordMatcher::new().args_contains("connected");
+    /// let record = logger.pop_matching(&matcher).unwrap();
+    /// assert_eq!(record.args(), "user 42 connected");
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn pop_matching<M>(&self, matcher: &M) -> Option<Record>
+    where
+        M: Matcher + ?Sized,
+    {
+        self.remove_first(|r| matcher.matches(r))
+    }
+
+    /// Wait until an event matching `pred` is in the event queue, then remove
+    /// and return it.
+    ///
+    /// Returns `None` if no matching event arrives within `timeout`. The order
+    /// of the remaining events is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let logger = Logger::start();
+    /// thread::spawn(|| log::info!("done"));
+    ///
+    /// let record = logger.wait_for(|r| r.args() == "done", Duration::from_secs(1));
+    /// assert!(record.is_some());
+    /// ```
+    pub fn wait_for<F>(&self, pred: F, timeout: Duration) -> Option<Record>
+    where
+        F: Fn(&Record) -> bool,
+    {
+        // Wait without a deadline if the timeout is too long to represent.
+        let deadline = Instant::now().checked_add(timeout);
+        let mut events = self.events();
+        loop {
+            if let Some(index) = events.iter().position(&pred) {
+                let record = events.remove(index);
+                self.queue.remember(&record);
+                return record;
+            }
+            events = self.wait_pushed(events, deadline)?;
+        }
+    }
+
+    /// Pop an event from the front of the event queue, waiting up to
+    /// `timeout` for one to arrive if the queue is empty.
+    ///
+    /// Returns immediately if an event is already queued.
+    pub fn pop_blocking(&self, timeout: Duration) -> Option<Record> {
+        self.wait_for(|_| true, timeout)
+    }
+
+    /// Wait until the event queue holds at least `n` events, or `timeout`
+    /// elapses.
+    ///
+    /// Returns whether the count was reached. No events are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let logger = Logger::start();
+    /// thread::spawn(|| {
+    ///     log::info!("one");
+    ///     log::info!("two");
+    /// });
+    ///
+    /// assert!(logger.wait_for_count(2, Duration::from_secs(1)));
+    /// ```
+    pub fn wait_for_count(&self, n: usize, timeout: Duration) -> bool {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut events = self.events();
+        loop {
+            if events.len() >= n {
+                return true;
+            }
+            events = match self.wait_pushed(events, deadline) {
+                Some(events) => events,
+                None => return false,
+            };
+        }
+    }
+
+    /// Count the events in the event queue that match `pred`, without
+    /// removing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("cache miss");
+    /// log::info!("fetching");
+    /// log::info!("cache hit");
+    ///
+    /// assert_eq!(logger.count_matching(|r| r.args().contains("cache")), 2);
+    /// assert_eq!(logger.len(), 3);
+    /// ```
+    pub fn count_matching<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&Record) -> bool,
+    {
+        self.events().iter().filter(|r| pred(r)).count()
+    }
+
+    /// Count the events in the event queue at `level`, without removing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::Level;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::warn!("slow");
+    /// log::error!("failed");
+    ///
+    /// assert_eq!(logger.count_level(Level::Warn), 1);
+    /// assert_eq!(logger.count_at_least(Level::Warn), 2);
+    /// ```
+    pub fn count_level(&self, level: Level) -> usize {
+        self.count_matching(|r| r.is_level(level))
+    }
+
+    /// Count the events in the event queue at `level` or more severe, without
+    /// removing them.
+    pub fn count_at_least(&self, level: Level) -> usize {
+        self.count_matching(|r| r.at_least(level))
+    }
+
+    /// Count the events in the event queue per level, without removing them.
+    ///
+    /// Levels without any events are left out of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use log::Level;
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::warn!("slow");
+    /// log::warn!("slower");
+    /// log::info!("done");
+    ///
+    /// let counts = logger.level_counts();
+    /// assert_eq!(counts[&Level::Warn], 2);
+    /// assert_eq!(counts.get(&Level::Error), None);
+    /// ```
+    pub fn level_counts(&self) -> HashMap<Level, usize> {
+        let mut counts = HashMap::new();
+        for record in self.events().iter() {
+            *counts.entry(record.level()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Compute summary statistics of the event queue, without removing any
+    /// events.
+    ///
+    /// See [`LogStats`] for an example.
+    pub fn stats(&self) -> LogStats {
+        LogStats::new(self.events().iter())
+    }
+
+    /// Returns the number of events discarded because the event queue was
+    /// full since the logger was started.
+    ///
+    /// See [`Builder::capacity`].
+    pub fn dropped_count(&self) -> usize {
+        self.queue.dropped.load(Ordering::Relaxed)
     }
 
     /// Returns the number of elements in the `Logger`.
-    pub fn len(&mut self) -> usize {
-        EVENTS.lock().unwrap().len()
+    pub fn len(&self) -> usize {
+        self.events().len()
     }
 
     /// Returns `true` if the `Logger` is empty.
-    pub fn is_empty(&mut self) -> bool {
-        EVENTS.lock().unwrap().is_empty()
+    pub fn is_empty(&self) -> bool {
+        self.events().is_empty()
+    }
+
+    /// Assert that the event queue is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a message listing the remaining events if the queue isn't
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("expected");
+    ///
+    /// assert_eq!(logger.pop_front().unwrap().args(), "expected");
+    /// logger.assert_empty();
+    /// ```
+    #[track_caller]
+    pub fn assert_empty(&self) {
+        let events = self.events();
+        if events.is_empty() {
+            return;
+        }
+        let mut msg = format!("expected no records, found {}:", events.len());
+        for record in events.iter() {
+            msg.push_str(&format!("\n    {}", record));
+        }
+        drop(events);
+        panic!("{}", msg);
+    }
+
+    /// Remove the events in `range` from the event queue, and return them in
+    /// order.
+    ///
+    /// Use `drain(..)` to remove every event. The events outside of `range`
+    /// are kept in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, like [`VecDeque::drain`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("hello");
+    /// log::info!("world");
+    ///
+    /// let records = logger.drain(..);
+    /// assert_eq!(records[0].args(), "hello");
+    /// assert_eq!(records[1].args(), "world");
+    /// assert!(logger.is_empty());
+    /// ```
+    pub fn drain<R>(&self, range: R) -> Vec<Record>
+    where
+        R: RangeBounds<usize>,
+    {
+        let mut events = self.events();
+        let range = match checked_range(&range, events.len()) {
+            Some(range) => range,
+            None => {
+                // Don't poison the queue by panicking while it's locked.
+                let len = events.len();
+                drop(events);
+                panic!("drain range out of bounds for a queue of {} events", len);
+            }
+        };
+        let records: Vec<_> = events.drain(range).collect();
+        self.queue.remember(&records);
+        records
+    }
+
+    /// Keep only the events that match `pred`, preserving their order.
+    ///
+    /// This is useful to discard events from dependencies before making
+    /// assertions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(target: "hyper", "polling");
+    /// log::info!(target: "my_crate::db", "connected");
+    ///
+    /// logger.retain(|r| r.target().starts_with("my_crate"));
+    /// assert_eq!(logger.len(), 1);
+    /// ```
+    pub fn retain<F>(&self, mut pred: F)
+    where
+        F: FnMut(&Record) -> bool,
+    {
+        self.events().retain(|record| {
+            let keep = pred(record);
+            if !keep {
+                self.queue.remember(Some(record));
+            }
+            keep
+        });
+    }
+
+    /// Remove every event from the event queue, and format them as a
+    /// deterministic string for snapshot testing.
+    ///
+    /// Each event is formatted on its own line as
+    /// `LEVEL target: args [k=v, ...]`, with the key-value pairs sorted by
+    /// key and string values quoted. The brackets are left out for events
+    /// without key-value pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(target: "db", user = "chashu", attempt = 2; "connected");
+    /// log::warn!(target: "db", "slow query");
+    ///
+    /// assert_eq!(
+    ///     logger.snapshot(),
+    ///     "INFO db: connected [attempt=2, user=\"chashu\"]\nWARN db: slow query"
+    /// );
+    /// assert!(logger.is_empty());
+    /// ```
+    pub fn snapshot(&self) -> String {
+        let mut out = String::new();
+        for (i, record) in self.drain(..).into_iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(&format!(
+                "{} {}: {}",
+                record.level, record.target, record.args
+            ));
+            let mut pairs: Vec<_> = record.key_values.iter().collect();
+            pairs.sort_by(|a, b| a.0.cmp(&b.0));
+            for (j, (key, value)) in pairs.into_iter().enumerate() {
+                out.push_str(if j == 0 { " [" } else { ", " });
+                match value {
+                    Value::String(v) => out.push_str(&format!("{}={:?}", key, v)),
+                    value => out.push_str(&format!("{}={}", key, value)),
+                }
+            }
+            if !record.key_values.is_empty() {
+                out.push(']');
+            }
+        }
+        out
+    }
+
+    /// Format every event in the event queue on its own line, prefixed with
+    /// its index, without removing them.
+    ///
+    /// Events use the `Display` formatting of [`Record`], which lists the
+    /// key-value pairs in the order they were logged. This is useful to
+    /// attach everything that was captured to a panic message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::warn!(target: "my_crate", "low disk space");
+    /// log::info!(target: "my_crate", free = 3; "cleaning up");
+    ///
+    /// assert_eq!(
+    ///     logger.dump(),
+    ///     "0: [WARN my_crate] low disk space\n1: [INFO my_crate] cleaning up {free=3}"
+    /// );
+    /// assert!(logger.len() == 2, "logs:\n{}", logger.dump());
+    /// ```
+    pub fn dump(&self) -> String {
+        let events = self.events();
+        let lines: Vec<_> = events
+            .iter()
+            .enumerate()
+            .map(|(i, record)| format!("{}: {}", i, record))
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Remove every event from the event queue, and write them to `writer`
+    /// one per line, using the `Display` formatting of [`Record`].
+    ///
+    /// This is useful to print everything that was captured when a test
+    /// fails. The queue is drained before writing, so events are discarded
+    /// even if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(target: "db", "connected");
+    /// log::warn!(target: "db", "slow query");
+    ///
+    /// let mut out = Vec::new();
+    /// logger.flush_to_writer(&mut out).unwrap();
+    /// assert_eq!(out, b"[INFO db] connected\n[WARN db] slow query\n");
+    /// assert!(logger.is_empty());
+    /// ```
+    pub fn flush_to_writer<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write + ?Sized,
+    {
+        for record in self.drain(..) {
+            writeln!(writer, "{}", record)?;
+        }
+        Ok(())
+    }
+
+    /// Remove every event from the event queue, grouped by target.
+    ///
+    /// Events in each group keep their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(target: "db", "connected");
+    /// log::info!(target: "http", "listening");
+    /// log::info!(target: "db", "migrated");
+    ///
+    /// let groups = logger.by_target();
+    /// assert_eq!(groups["db"].len(), 2);
+    /// assert_eq!(groups["http"][0].args(), "listening");
+    /// assert!(logger.is_empty());
+    /// ```
+    pub fn by_target(&self) -> BTreeMap<String, Vec<Record>> {
+        group_by_target(self.drain(..))
+    }
+
+    /// Return a copy of the event queue with runs of equal consecutive events
+    /// collapsed into one, along with the length of each run.
+    ///
+    /// Events are equal if their message, level, target and key-value pairs
+    /// are. Use [`Logger::dedup_by`] to compare them differently. No events
+    /// are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("connecting");
+    /// for _ in 0..3 {
+    ///     log::warn!("connection refused, retrying");
+    /// }
+    /// log::info!("connected");
+    ///
+    /// let runs = logger.dedup();
+    /// assert_eq!(runs.len(), 3);
+    /// assert_eq!(runs[1].0.args(), "connection refused, retrying");
+    /// assert_eq!(runs[1].1, 3);
+    /// ```
+    pub fn dedup(&self) -> Vec<(Record, usize)> {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Return a copy of the event queue with runs of consecutive events for
+    /// which `same` returns `true` collapsed into the first event of the run,
+    /// along with the length of each run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::warn!(attempt = 1; "retrying");
+    /// log::warn!(attempt = 2; "retrying");
+    ///
+    /// // Ignore the key-value pairs.
+    /// let runs = logger.dedup_by(|a, b| {
+    ///     a.args() == b.args() && a.level() == b.level() && a.target() == b.target()
+    /// });
+    /// assert_eq!(runs.len(), 1);
+    /// assert_eq!(runs[0].1, 2);
+    /// ```
+    pub fn dedup_by<F>(&self, mut same: F) -> Vec<(Record, usize)>
+    where
+        F: FnMut(&Record, &Record) -> bool,
+    {
+        let mut runs: Vec<(Record, usize)> = Vec::new();
+        for record in self.events().iter() {
+            match runs.last_mut() {
+                Some((first, count)) if same(first, record) => *count += 1,
+                _ => runs.push((record.clone(), 1)),
+            }
+        }
+        runs
+    }
+
+    /// Return a copy of every event in the event queue grouped by target,
+    /// without removing them.
+    ///
+    /// Events in each group keep their order.
+    pub fn records_by_target(&self) -> BTreeMap<String, Vec<Record>> {
+        group_by_target(self.records())
+    }
+
+    /// Remove every event that matches `pred`, and return them in order.
+    ///
+    /// The order of the remaining events is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!(target: "my_crate::db", "connected");
+    /// log::info!(target: "my_crate::http", "listening");
+    /// log::info!(target: "my_crate::db", "migrated");
+    ///
+    /// let db = logger.extract(|r| r.target() == "my_crate::db");
+    /// assert_eq!(db.len(), 2);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "listening");
+    /// ```
+    pub fn extract<F>(&self, mut pred: F) -> Vec<Record>
+    where
+        F: FnMut(&Record) -> bool,
+    {
+        let mut events = self.events();
+        let mut extracted = Vec::new();
+        let mut kept = VecDeque::with_capacity(events.len());
+        for record in events.drain(..) {
+            if pred(&record) {
+                extracted.push(record);
+            } else {
+                kept.push_back(record);
+            }
+        }
+        *events = kept;
+        self.queue.remember(&extracted);
+        extracted
+    }
+
+    /// Keep the oldest `len` events, and drop the rest.
+    ///
+    /// Does nothing if the queue holds `len` events or fewer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("one");
+    /// log::info!("two");
+    ///
+    /// logger.truncate(1);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "one");
+    /// assert!(logger.is_empty());
+    /// ```
+    pub fn truncate(&self, len: usize) {
+        let mut events = self.events();
+        self.queue.remember(events.iter().skip(len));
+        events.truncate(len);
+    }
+
+    /// Keep the newest `len` events, and drop the rest.
+    ///
+    /// Does nothing if the queue holds `len` events or fewer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("one");
+    /// log::info!("two");
+    ///
+    /// logger.keep_last(1);
+    /// assert_eq!(logger.pop_front().unwrap().args(), "two");
+    /// assert!(logger.is_empty());
+    /// ```
+    pub fn keep_last(&self, len: usize) {
+        let mut events = self.events();
+        let excess = events.len().saturating_sub(len);
+        self.queue.remember(events.range(..excess));
+        events.drain(..excess);
+    }
+
+    /// Stop capturing events, and return every event in the event queue in
+    /// order.
+    ///
+    /// Events logged afterwards are discarded, until a new logger is started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::start();
+    /// log::info!("hello");
+    ///
+    /// let records = logger.finish();
+    /// assert_eq!(records[0].args(), "hello");
+    /// log::info!("discarded");
+    /// ```
+    pub fn finish(self) -> Vec<Record> {
+        let mut events = self.events();
+        self.queue.finished.store(true, Ordering::Relaxed);
+        let records: Vec<_> = events.drain(..).collect();
+        self.queue.remember(&records);
+        records
+    }
+
+    /// Return a copy of the events removed from the event queue, in the order
+    /// they were removed.
+    ///
+    /// This is empty unless the logger was started with
+    /// [`Builder::history`].
+    pub fn history(&self) -> Vec<Record> {
+        match &*self
+            .queue
+            .history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+        {
+            Some(history) => history.records.iter().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Remove every event from the history.
+    pub fn clear_history(&self) {
+        if let Some(history) = &mut *self
+            .queue
+            .history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+        {
+            history.records.clear();
+        }
+    }
+
+    /// Clear the event queue, and return a guard that clears it again when
+    /// dropped.
+    ///
+    /// See [`CaptureGuard`] for an example.
+    pub fn capture_scope(&mut self) -> CaptureGuard<'_> {
+        CaptureGuard::new(self)
+    }
+
+    /// Remove all events from the event queue, and return how many were
+    /// removed.
+    ///
+    /// This happens under a single lock, so events logged by other threads
+    /// after the queue was cleared are kept.
+    pub fn clear(&self) -> usize {
+        let mut events = self.events();
+        let len = events.len();
+        self.queue.remember(events.iter());
+        events.clear();
+        len
+    }
+}
+
+/// Resolve `range` against a queue of `len` events, or return `None` if it's
+/// out of bounds.
+fn checked_range(range: &impl RangeBounds<usize>, len: usize) -> Option<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start <= end && end <= len {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+/// Group `records` by target, preserving their order within each group.
+fn group_by_target(records: Vec<Record>) -> BTreeMap<String, Vec<Record>> {
+    let mut groups = BTreeMap::<String, Vec<Record>>::new();
+    for record in records {
+        groups
+            .entry(record.target.clone())
+            .or_default()
+            .push(record);
+    }
+    groups
+}
+
+/// Configure which events a [`Logger`] captures.
+///
+/// Created through [`Logger::builder`].
+#[derive(Debug)]
+pub struct Builder {
+    config: Config,
+    isolated: bool,
+    preserve_level: bool,
+    history: Option<History>,
+    #[cfg(feature = "tracing")]
+    tracing: bool,
+}
+
+impl Builder {
+    /// Only capture events at `level` or above.
+    ///
+    /// Unless [`Builder::preserve_level`] is set, this is also used as the
+    /// global max level while the logger runs.
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.config.level = level;
+        self
+    }
+
+    /// Only capture events whose target starts with `prefix`.
+    ///
+    /// This can be called multiple times to capture events matching any of
+    /// the prefixes.
+    pub fn target(mut self, prefix: impl Into<String>) -> Self {
+        self.config.targets.push(prefix.into());
+        self
+    }
+
+    /// Don't capture events whose target starts with `prefix`.
+    ///
+    /// This can be called multiple times to exclude several prefixes, and
+    /// takes precedence over [`Builder::target`].
+    pub fn exclude_target(mut self, prefix: impl Into<String>) -> Self {
+        self.config.excluded_targets.push(prefix.into());
+        self
+    }
+
+    /// Truncate message bodies longer than `len` bytes.
+    ///
+    /// Messages are cut at the nearest character boundary at or below `len`,
+    /// and marked through [`Record::truncated`]. By default messages are never
+    /// truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::builder().max_args_len(5).start();
+    /// log::info!("hello world");
+    ///
+    /// let record = logger.pop_front().unwrap();
+    /// assert_eq!(record.args(), "hello");
+    /// assert!(record.truncated());
+    /// ```
+    pub fn max_args_len(mut self, len: usize) -> Self {
+        self.config.max_args_len = Some(len);
+        self
+    }
+
+    /// Call `sink` with every captured event, as it is logged.
+    ///
+    /// The event is still pushed onto the event queue. `sink` runs on the
+    /// thread that logged the event, after the event was queued and without
+    /// holding any locks. A panic in `sink` propagates to the logging call.
+    pub fn sink<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(&Record) + Send + Sync + 'static,
+    {
+        self.config.sink = Some(Sink(Arc::new(sink)));
+        self
+    }
+
+    /// Keep at most `capacity` events in the event queue.
+    ///
+    /// What happens to events logged while the queue is full is selected
+    /// through [`Builder::overflow`]. Either way they're counted by
+    /// [`Logger::dropped_count`]. By default the queue is unbounded.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.config.capacity = Some(capacity);
+        self
+    }
+
+    /// Select which events are dropped when the event queue is full.
+    ///
+    /// Defaults to [`OverflowPolicy::DropOldest`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::{Logger, OverflowPolicy};
+    ///
+    /// let logger = Logger::builder()
+    ///     .capacity(1)
+    ///     .overflow(OverflowPolicy::DropNewest)
+    ///     .start();
+    /// log::info!("one");
+    /// log::info!("two");
+    ///
+    /// assert_eq!(logger.pop_front().unwrap().args(), "one");
+    /// ```
+    pub fn overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.config.overflow = policy;
+        self
+    }
+
+    /// Don't change the global max level when starting the logger.
+    ///
+    /// By default the max level is set to [`Builder::level`], which is `Trace`
+    /// unless configured, so every event that may be captured reaches the
+    /// logger. With this option events below [`log::max_level`] are
+    /// filtered out by the `log` macros and never captured, so tests observe
+    /// the level filtering configured by the code under test. Dropping the
+    /// logger doesn't restore the max level either.
+    pub fn preserve_level(mut self) -> Self {
+        self.preserve_level = true;
+        self
+    }
+
+    /// Only capture events logged on the thread that starts the logger.
+    ///
+    /// Events logged on other threads, such as worker threads spawned by the
+    /// code under test, aren't attributed to the isolated logger. They go to
+    /// the global queue read by non-isolated loggers instead.
+    ///
+    /// The level and target filters are still shared by all loggers, so
+    /// parallel tests should start their loggers with the same
+    /// configuration. Dropping an isolated logger doesn't restore the global
+    /// max level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    /// use std::thread;
+    ///
+    /// let logger = Logger::builder().isolated().start();
+    /// log::info!("captured");
+    /// thread::spawn(|| log::info!("not captured")).join().unwrap();
+    ///
+    /// assert_eq!(logger.pop_front().unwrap().args(), "captured");
+    /// assert!(logger.is_empty());
+    /// ```
+    pub fn isolated(mut self) -> Self {
+        self.isolated = true;
+        self
+    }
+
+    /// Keep a history of the events removed from the event queue.
+    ///
+    /// Every event that is popped, drained, or otherwise removed is also
+    /// appended to the history, which can be read with [`Logger::history`].
+    /// This makes it possible to assert that something was never logged,
+    /// after earlier assertions consumed part of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::builder().history().start();
+    /// log::info!("hello");
+    /// log::info!("world");
+    /// assert_eq!(logger.pop_front().unwrap().args(), "hello");
+    ///
+    /// let seen = logger.history().into_iter().chain(logger.records());
+    /// assert!(!seen.into_iter().any(|r| r.args().contains("secret")));
+    /// ```
+    pub fn history(mut self) -> Self {
+        self.history = Some(History::default());
+        self
+    }
+
+    /// Keep a history of at most `limit` events removed from the event queue,
+    /// dropping the oldest first.
+    ///
+    /// See [`Builder::history`].
+    pub fn history_limit(mut self, limit: usize) -> Self {
+        self.history = Some(History {
+            records: VecDeque::new(),
+            limit: Some(limit),
+        });
+        self
+    }
+
+    /// Also capture `tracing` events, by installing [`TracingLayer`] as the
+    /// global default subscriber.
+    ///
+    /// Starting fails if a different global subscriber has already been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logtest::Logger;
+    ///
+    /// let logger = Logger::builder().tracing().start();
+    /// tracing::info!(color = "blue", "hello");
+    ///
+    /// let record = logger.pop_front().unwrap();
+    /// assert_eq!(record.args(), "hello");
+    /// assert_eq!(record.key_value("color").unwrap(), "blue");
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn tracing(mut self) -> Self {
+        self.tracing = true;
+        self
+    }
+
+    /// Start listening for events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a different global logger has already been installed. Use
+    /// [`Builder::try_start`] to handle this case.
+    pub fn start(self) -> Logger {
+        match self.try_start() {
+            Ok(logger) => logger,
+            Err(err) => panic!(
+                "failed to start the test logger: {}; use `try_start` to handle this",
+                err
+            ),
+        }
+    }
+
+    /// Start listening for events, returning an error if a different global
+    /// logger has already been installed.
+    pub fn try_start(self) -> Result<Logger, StartError> {
+        Logger::install(self)
+    }
+}
+
+/// A position in the event queue.
+///
+/// Created through [`Logger::checkpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Checkpoint(u64);
+
+/// Which events are dropped when the event queue is full.
+///
+/// See [`Builder::capacity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drop the oldest event to make room for the new one.
+    DropOldest,
+    /// Drop the new event.
+    DropNewest,
+}
+
+/// The error returned by [`Logger::try_start`] when a different global logger
+/// has already been installed.
+#[derive(Debug)]
+pub struct StartError(StartErrorKind);
+
+/// What was already installed.
+#[derive(Debug)]
+enum StartErrorKind {
+    Log(log::SetLoggerError),
+    #[cfg(feature = "tracing")]
+    Tracing(tracing::subscriber::SetGlobalDefaultError),
+}
+
+impl From<StartErrorKind> for StartError {
+    fn from(kind: StartErrorKind) -> Self {
+        StartError(kind)
+    }
+}
+
+impl fmt::Display for StartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            StartErrorKind::Log(_) => {
+                write!(f, "a different global logger has already been installed")
+            }
+            #[cfg(feature = "tracing")]
+            StartErrorKind::Tracing(_) => write!(
+                f,
+                "a different global tracing subscriber has already been installed"
+            ),
+        }
     }
 }
 
+impl Error for StartError {}
+
+/// Pop the next record from a [`Logger`] and assert that it has the given
+/// message, and optionally the given level.
+///
+/// The panic message shows the record that was found instead.
+///
+/// # Examples
+///
+/// ```
+/// use log::Level;
+/// use logtest::{assert_logged, Logger};
+///
+/// let logger = Logger::start();
+/// log::info!("hello");
+/// log::warn!("world");
+///
+/// assert_logged!(logger, "hello");
+/// assert_logged!(logger, Level::Warn, "world");
+/// ```
+#[macro_export]
+macro_rules! assert_logged {
+    ($logger:expr, $level:expr, $args:expr $(,)?) => {{
+        let level = $level;
+        let args: &str = $args;
+        match $logger.pop_front() {
+            Some(record) => assert!(
+                record.level() == level && record.args() == args,
+                "expected `[{}] {}` to be logged, found `{}`",
+                level,
+                args,
+                record,
+            ),
+            None => panic!(
+                "expected `[{}] {}` to be logged, found no records",
+                level, args,
+            ),
+        }
+    }};
+    ($logger:expr, $args:expr $(,)?) => {{
+        let args: &str = $args;
+        match $logger.pop_front() {
+            Some(record) => assert!(
+                record.args() == args,
+                "expected `{}` to be logged, found `{}`",
+                args,
+                record,
+            ),
+            None => panic!("expected `{}` to be logged, found no records", args),
+        }
+    }};
+}
+
+/// Assert that a [`Record`] has a key-value pair with the given key and
+/// value.
+///
+/// Values are compared as a [`Value`], or as a string against the value's
+/// `Display` output, so string values don't need to be quoted. The panic
+/// message lists the keys that were found instead.
+///
+/// # Examples
+///
+/// ```
+/// use logtest::{assert_kv, Logger};
+///
+/// let logger = Logger::start();
+/// log::info!(color = "blue", retries = 3; "hello");
+///
+/// let record = logger.pop_front().unwrap();
+/// assert_kv!(record, "color" => "blue");
+/// assert_kv!(record, "retries" => 3);
+/// assert_kv!(record, "retries" => "3");
+/// ```
+#[macro_export]
+macro_rules! assert_kv {
+    ($record:expr, $key:expr => $value:expr $(,)?) => {{
+        let record: &$crate::Record = &$record;
+        let key: &str = $key;
+        let expected = $crate::Value::from($value);
+        match record.key_value(key) {
+            Some(actual) => assert!(
+                *actual == expected || *actual == *expected.to_string(),
+                "expected `{}` to be `{}`, found `{}`",
+                key,
+                expected,
+                actual,
+            ),
+            None => panic!(
+                "expected key `{}` to be logged, found keys [{}]",
+                key,
+                record
+                    .key_values()
+                    .iter()
+                    .map(|(k, _)| k.as_str())
+                    .collect::<::std::vec::Vec<_>>()
+                    .join(", "),
+            ),
+        }
+    }};
+}
+
 /// Create a new instance of `Logger` and start listening for events.
 pub fn start() -> Logger {
     Logger::start()
 }
 
+impl Drop for Logger {
+    fn drop(&mut self) {
+        ACTIVE.fetch_sub(1, Ordering::Relaxed);
+        if let Some(level) = self.prior_level {
+            log::set_max_level(level);
+        }
+        // Stop routing this thread's events to the queue if it was isolated.
+        if self.isolated {
+            let _ = LOCAL.try_with(|local| {
+                let mut local = local.borrow_mut();
+                if local.as_ref().is_some_and(|q| Arc::ptr_eq(q, &self.queue)) {
+                    *local = None;
+                }
+            });
+        }
+    }
+}
+
+/// Create another handle to the same event queue.
+///
+/// All handles share one queue, so an event popped through one handle is no
+/// longer seen by the others. Dropping a cloned handle leaves the global max
+/// level and the capture mode untouched; only the handle returned when the
+/// logger was started restores them.
+///
+/// # Examples
+///
+/// ```
+/// use logtest::Logger;
+///
+/// let logger = Logger::start();
+/// let handle = logger.clone();
+/// log::info!("hello");
+///
+/// assert_eq!(handle.pop_front().unwrap().args(), "hello");
+/// assert!(logger.is_empty());
+/// ```
+impl Clone for Logger {
+    fn clone(&self) -> Self {
+        ACTIVE.fetch_add(1, Ordering::Relaxed);
+        Self {
+            prior_level: None,
+            queue: self.queue.clone(),
+            isolated: false,
+        }
+    }
+}
+
+/// Summarizes the event queue without removing any events, in the format of
+/// [`LogStats`].
+///
+/// An empty queue is written as `Logger { empty }`. This never panics: a
+/// queue that is locked by a [`RecordsGuard`] on the current thread is
+/// written as `Logger { locked }`, and a poisoned queue as
+/// `Logger { poisoned }`.
+///
+/// # Examples
+///
+/// ```
+/// use logtest::Logger;
+///
+/// let logger = Logger::start();
+/// assert_eq!(logger.to_string(), "Logger { empty }");
+///
+/// log::warn!(target: "my_crate", "slow");
+/// log::info!(target: "hyper", "polling");
+/// assert_eq!(
+///     logger.to_string(),
+///     "Logger { 2 records; levels: WARN=1 INFO=1; targets: hyper=1 my_crate=1 }"
+/// );
+/// ```
+impl fmt::Display for Logger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Locking the queue would deadlock while this thread holds a guard.
+        if records_guard::is_held() {
+            return f.write_str("Logger { locked }");
+        }
+        let events = match self.queue.events.lock() {
+            Ok(events) => events,
+            Err(_) => return f.write_str("Logger { poisoned }"),
+        };
+        if events.is_empty() {
+            return f.write_str("Logger { empty }");
+        }
+        write!(f, "Logger {{ {} }}", LogStats::new(events.iter()))
+    }
+}
+
 impl Iterator for Logger {
     type Item = Record;
     fn next(&mut self) -> Option<Self::Item> {
-        self.pop()
+        self.pop_front()
+    }
+}
+
+impl DoubleEndedIterator for Logger {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.pop_back()
     }
 }
@@ -0,0 +1,174 @@
+use crate::{Queue, Record};
+use log::Metadata;
+use std::ops::RangeBounds;
+use std::sync::{Arc, PoisonError};
+use std::time::Instant;
+
+/// A logger with its own event queue, that isn't installed globally.
+///
+/// Unlike [`Logger`](crate::Logger), which installs itself as the process-wide
+/// logger and shares a single queue, a `LocalLogger` only captures the events
+/// passed to its [`log::Log`] implementation. This allows unit tests to
+/// capture events in parallel without interfering with each other, as long as
+/// the code under test logs through a logger it is handed rather than through
+/// the `log` macros. It can also be installed with [`log::set_boxed_logger`]
+/// in a harness that controls the global logger itself.
+///
+/// A `LocalLogger` captures every event it is passed, and doesn't support the
+/// filtering options of [`Builder`](crate::Builder). Clones share the same
+/// queue, so one clone can be handed to the code under test while another is
+/// used to make assertions.
+///
+/// # Examples
+///
+/// ```
+/// use log::Log;
+/// use logtest::LocalLogger;
+///
+/// fn connect(logger: &dyn Log) {
+///     logger.log(
+///         &log::Record::builder()
+///             .args(format_args!("connected"))
+///             .level(log::Level::Info)
+///             .build(),
+///     );
+/// }
+///
+/// let logger = LocalLogger::new();
+/// connect(&logger);
+/// assert_eq!(logger.pop_front().unwrap().args(), "connected");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LocalLogger {
+    queue: Arc<Queue>,
+    started_at: Instant,
+}
+
+impl LocalLogger {
+    /// Create a new `LocalLogger` with an empty event queue.
+    pub fn new() -> Self {
+        Self {
+            queue: Arc::default(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Pop an event from the front of the event queue.
+    #[must_use]
+    pub fn pop_front(&self) -> Option<Record> {
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front()
+    }
+
+    /// Pop an event from the back of the event queue.
+    #[must_use]
+    pub fn pop_back(&self) -> Option<Record> {
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_back()
+    }
+
+    /// Return a copy of the event at the front of the event queue, without
+    /// removing it.
+    pub fn peek(&self) -> Option<Record> {
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .front()
+            .cloned()
+    }
+
+    /// Return a copy of every event in the event queue, without removing them.
+    pub fn records(&self) -> Vec<Record> {
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Remove the events in `range` from the event queue, and return them in
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, like
+    /// [`VecDeque::drain`](std::collections::VecDeque::drain).
+    pub fn drain<R>(&self, range: R) -> Vec<Record>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(range)
+            .collect()
+    }
+
+    /// Remove all events from the event queue, and return how many were
+    /// removed.
+    pub fn clear(&self) -> usize {
+        let mut events = self
+            .queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let len = events.len();
+        events.clear();
+        len
+    }
+
+    /// Returns the number of events in the event queue.
+    pub fn len(&self) -> usize {
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns `true` if the event queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+}
+
+impl Default for LocalLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl log::Log for LocalLogger {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        let mut captured = Record::capture(record, None);
+        let mut events = self
+            .queue
+            .events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        captured.stamp(&events, Some(self.started_at));
+        events.push_back(captured);
+        drop(events);
+        self.queue.pushed.notify_all();
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,123 @@
+use crate::{Record, Value};
+use log::Level;
+
+/// A condition a record can be matched against.
+///
+/// This is implemented by [`RecordMatcher`], and with the `regex` feature by
+/// `regex::Regex`, which matches the message body.
+pub trait Matcher {
+    /// Returns `true` if `record` matches.
+    fn matches(&self, record: &Record) -> bool;
+}
+
+impl Matcher for RecordMatcher {
+    fn matches(&self, record: &Record) -> bool {
+        RecordMatcher::matches(self, record)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Matcher for regex::Regex {
+    fn matches(&self, record: &Record) -> bool {
+        record.args_matches(self)
+    }
+}
+
+/// Match records against a set of constraints.
+///
+/// A matcher without any constraints matches every record.
+///
+/// # Examples
+///
+/// ```
+/// use log::Level;
+/// use logtest::{Logger, RecordMatcher};
+///
+/// let mut logger = Logger::start();
+/// log::warn!(peer = "10.0.0.1"; "request timeout after 30s");
+///
+/// let matcher = RecordMatcher::new()
+///     .level(Level::Warn)
+///     .args_contains("timeout")
+///     .has_kv("peer");
+/// assert!(logger.any(&matcher));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RecordMatcher {
+    constraints: Vec<Constraint>,
+}
+
+/// A single constraint of a `RecordMatcher`.
+#[derive(Debug, Clone)]
+enum Constraint {
+    Level(Level),
+    ArgsEq(String),
+    ArgsContains(String),
+    Target(String),
+    TargetStartsWith(String),
+    HasKv(String),
+    KvEq(String, Value),
+}
+
+impl RecordMatcher {
+    /// Create a new matcher without any constraints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match records at `level`.
+    pub fn level(self, level: Level) -> Self {
+        self.with(Constraint::Level(level))
+    }
+
+    /// Only match records whose message equals `args`.
+    pub fn args_eq(self, args: impl Into<String>) -> Self {
+        self.with(Constraint::ArgsEq(args.into()))
+    }
+
+    /// Only match records whose message contains `needle`.
+    pub fn args_contains(self, needle: impl Into<String>) -> Self {
+        self.with(Constraint::ArgsContains(needle.into()))
+    }
+
+    /// Only match records whose target equals `target`.
+    pub fn target(self, target: impl Into<String>) -> Self {
+        self.with(Constraint::Target(target.into()))
+    }
+
+    /// Only match records whose target starts with `prefix`.
+    pub fn target_starts_with(self, prefix: impl Into<String>) -> Self {
+        self.with(Constraint::TargetStartsWith(prefix.into()))
+    }
+
+    /// Only match records with a key-value pair for `key`.
+    pub fn has_kv(self, key: impl Into<String>) -> Self {
+        self.with(Constraint::HasKv(key.into()))
+    }
+
+    /// Only match records with a key-value pair for `key` that equals `value`.
+    pub fn kv_eq(self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.with(Constraint::KvEq(key.into(), value.into()))
+    }
+
+    /// Returns `true` if `record` satisfies every constraint.
+    pub fn matches(&self, record: &Record) -> bool {
+        self.constraints.iter().all(|constraint| match constraint {
+            Constraint::Level(level) => record.level() == *level,
+            Constraint::ArgsEq(args) => record.args() == args,
+            Constraint::ArgsContains(needle) => record.args().contains(needle.as_str()),
+            Constraint::Target(target) => record.target() == target,
+            Constraint::TargetStartsWith(prefix) => record.target().starts_with(prefix.as_str()),
+            Constraint::HasKv(key) => record.key_value(key).is_some(),
+            Constraint::KvEq(key, value) => record
+                .key_values
+                .iter()
+                .any(|(k, v)| k == key && v == value),
+        })
+    }
+
+    fn with(mut self, constraint: Constraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+}
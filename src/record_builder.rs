@@ -0,0 +1,93 @@
+use crate::{Record, Value};
+use log::Level;
+
+/// Build a [`Record`] to compare captured records against.
+///
+/// Created through [`Record::builder`].
+///
+/// # Examples
+///
+/// ```
+/// use log::Level;
+/// use logtest::{Logger, Record};
+///
+/// let logger = Logger::start();
+/// log::warn!(path = "/"; "not found");
+///
+/// let record = logger.pop_front().unwrap();
+/// let expected = Record::builder()
+///     .args("not found")
+///     .level(Level::Warn)
+///     .target(module_path!())
+///     .key_value("path", "/");
+/// assert_eq!(record, expected.build());
+///
+/// // Only compare the fields that were set.
+/// assert!(Record::builder().args("not found").matches(&record));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RecordBuilder {
+    args: Option<String>,
+    level: Option<Level>,
+    target: Option<String>,
+    key_values: Option<Vec<(String, Value)>>,
+}
+
+impl RecordBuilder {
+    /// Set the message body.
+    pub fn args(mut self, args: impl Into<String>) -> Self {
+        self.args = Some(args.into());
+        self
+    }
+
+    /// Set the verbosity level.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Set the target.
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Append a structured key-value pair.
+    pub fn key_value(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.key_values
+            .get_or_insert_with(Vec::new)
+            .push((key.into(), value.into()));
+        self
+    }
+
+    /// Returns `true` if `record` matches every field that was set on the
+    /// builder.
+    pub fn matches(&self, record: &Record) -> bool {
+        self.args.as_ref().is_none_or(|args| *args == record.args)
+            && self.level.is_none_or(|level| level == record.level)
+            && self
+                .target
+                .as_ref()
+                .is_none_or(|target| *target == record.target)
+            && self
+                .key_values
+                .as_ref()
+                .is_none_or(|key_values| *key_values == record.key_values)
+    }
+
+    /// Build the `Record`.
+    ///
+    /// Fields that weren't set default to an empty message and target, the
+    /// `Info` level, and no key-value pairs. Capture metadata such as the
+    /// timestamp and thread is taken from the moment the record is built.
+    /// Built records weren't captured, so their [`Record::seq`] is 0, which
+    /// no captured record has.
+    pub fn build(self) -> Record {
+        Record::from_parts(
+            self.args.unwrap_or_default(),
+            self.level.unwrap_or(Level::Info),
+            self.target.unwrap_or_default(),
+            self.key_values.unwrap_or_default(),
+        )
+    }
+}
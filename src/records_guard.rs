@@ -0,0 +1,89 @@
+use crate::Record;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::MutexGuard;
+
+thread_local! {
+    /// Whether this thread holds a `RecordsGuard`.
+    static HELD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns `true` if this thread holds a `RecordsGuard`.
+pub(crate) fn is_held() -> bool {
+    HELD.try_with(Cell::get).unwrap_or(false)
+}
+
+/// Panic if this thread holds a `RecordsGuard`, instead of deadlocking on the
+/// event queue.
+pub(crate) fn assert_not_held() {
+    if is_held() {
+        panic!("the event queue was accessed while a `RecordsGuard` is held on this thread");
+    }
+}
+
+/// A locked view of the event queue that visits events by reference.
+///
+/// Created through [`Logger::lock`](crate::Logger::lock). No events can be
+/// captured while the guard is alive. Logging or reading the `Logger` on the
+/// thread holding the guard panics, since it would otherwise deadlock.
+///
+/// # Examples
+///
+/// ```
+/// use log::Level;
+/// use logtest::Logger;
+///
+/// let logger = Logger::start();
+/// log::info!("hello");
+/// log::warn!("world");
+///
+/// let records = logger.lock();
+/// let warnings = records.iter().filter(|r| r.level() == Level::Warn).count();
+/// assert_eq!(warnings, 1);
+/// ```
+#[derive(Debug)]
+pub struct RecordsGuard<'a> {
+    events: MutexGuard<'a, VecDeque<Record>>,
+}
+
+impl<'a> RecordsGuard<'a> {
+    pub(crate) fn new(events: MutexGuard<'a, VecDeque<Record>>) -> Self {
+        HELD.with(|held| held.set(true));
+        Self { events }
+    }
+
+    /// Iterate over the events in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Record> + ExactSizeIterator {
+        self.events.iter()
+    }
+
+    /// Returns the event at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&Record> {
+        self.events.get(index)
+    }
+
+    /// Returns the number of events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if there are no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a RecordsGuard<'_> {
+    type Item = &'a Record;
+    type IntoIter = std::collections::vec_deque::Iter<'a, Record>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
+    }
+}
+
+impl Drop for RecordsGuard<'_> {
+    fn drop(&mut self) {
+        let _ = HELD.try_with(|held| held.set(false));
+    }
+}
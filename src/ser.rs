@@ -0,0 +1,186 @@
+use crate::{Record, Value};
+use log::Level;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+use std::fmt;
+
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut record = serializer.serialize_struct("Record", 4)?;
+        record.serialize_field("args", &self.args)?;
+        record.serialize_field("level", self.level.as_str())?;
+        record.serialize_field("target", &self.target)?;
+        record.serialize_field("key_values", &KeyValues(&self.key_values))?;
+        record.end()
+    }
+}
+
+/// Deserializes the fields written by `Record`'s `Serialize` impl. Capture
+/// metadata is taken from the moment the record is deserialized.
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RecordVisitor;
+
+        impl<'de> Visitor<'de> for RecordVisitor {
+            type Value = Record;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a log record")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Record, A::Error> {
+                let mut args = None;
+                let mut level = None;
+                let mut target = None;
+                let mut key_values = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "args" => args = Some(map.next_value::<String>()?),
+                        "level" => {
+                            let value = map.next_value::<String>()?;
+                            level = Some(value.parse::<Level>().map_err(de::Error::custom)?);
+                        }
+                        "target" => target = Some(map.next_value::<String>()?),
+                        "key_values" => key_values = Some(map.next_value::<KeyValuesBuf>()?.0),
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                let mut record = Record::builder()
+                    .args(args.ok_or_else(|| de::Error::missing_field("args"))?)
+                    .level(level.ok_or_else(|| de::Error::missing_field("level"))?)
+                    .target(target.ok_or_else(|| de::Error::missing_field("target"))?)
+                    .build();
+                record.key_values =
+                    key_values.ok_or_else(|| de::Error::missing_field("key_values"))?;
+                Ok(record)
+            }
+        }
+
+        deserializer.deserialize_struct("Record", FIELDS, RecordVisitor)
+    }
+}
+
+/// The fields of a serialized `Record`.
+const FIELDS: &[&str] = &["args", "level", "target", "key_values"];
+
+/// Serializes key-value pairs as a map, in the order they were logged in.
+struct KeyValues<'a>(&'a [(String, Value)]);
+
+impl Serialize for KeyValues<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes key-value pairs from a map, preserving their order.
+struct KeyValuesBuf(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for KeyValuesBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyValuesVisitor;
+
+        impl<'de> Visitor<'de> for KeyValuesVisitor {
+            type Value = KeyValuesBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of key-value pairs")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(pair) = map.next_entry()? {
+                    pairs.push(pair);
+                }
+                Ok(KeyValuesBuf(pairs))
+            }
+        }
+
+        deserializer.deserialize_map(KeyValuesVisitor)
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::String(v) => serializer.serialize_str(v),
+            Value::Debug(v) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("debug", v)?;
+                map.end()
+            }
+            Value::I64(v) => serializer.serialize_i64(*v),
+            Value::U64(v) => serializer.serialize_u64(*v),
+            Value::F64(v) => serializer.serialize_f64(*v),
+            Value::Bool(v) => serializer.serialize_bool(*v),
+            Value::Null => serializer.serialize_unit(),
+        }
+    }
+}
+
+/// `Value::Debug` is written as `{"debug": "..."}`, to tell it apart from
+/// `Value::String`.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string, number, boolean, null or debug value")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+                Ok(Value::Bool(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+                Ok(Value::I64(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+                Ok(Value::U64(v))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+                Ok(Value::F64(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+                Ok(Value::String(v.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+                Ok(Value::String(v))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+                let value = match map.next_key::<String>()? {
+                    Some(key) if key == "debug" => Value::Debug(map.next_value()?),
+                    Some(key) => return Err(de::Error::unknown_field(&key, &["debug"])),
+                    None => return Err(de::Error::missing_field("debug")),
+                };
+                if let Some(key) = map.next_key::<String>()? {
+                    return Err(de::Error::unknown_field(&key, &["debug"]));
+                }
+                Ok(value)
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
@@ -0,0 +1,92 @@
+use crate::Record;
+use log::Level;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Summary statistics of the captured events.
+///
+/// Created through [`Logger::stats`](crate::Logger::stats).
+///
+/// # Examples
+///
+/// ```
+/// use log::Level;
+/// use logtest::Logger;
+///
+/// let logger = Logger::start();
+/// log::info!(target: "my_crate::db", "connected");
+/// log::warn!(target: "my_crate::http", "slow request");
+///
+/// let stats = logger.stats();
+/// assert_eq!(stats.total(), 2);
+/// assert_eq!(stats.level(Level::Warn), 1);
+/// assert_eq!(stats.target("my_crate::db"), 1);
+/// assert_eq!(
+///     stats.to_string(),
+///     "2 records; levels: WARN=1 INFO=1; targets: my_crate::db=1 my_crate::http=1"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogStats {
+    total: usize,
+    levels: BTreeMap<Level, usize>,
+    targets: BTreeMap<String, usize>,
+}
+
+impl LogStats {
+    pub(crate) fn new<'a>(records: impl Iterator<Item = &'a Record>) -> Self {
+        let mut stats = Self::default();
+        for record in records {
+            stats.total += 1;
+            *stats.levels.entry(record.level()).or_insert(0) += 1;
+            *stats.targets.entry(record.target().to_owned()).or_insert(0) += 1;
+        }
+        stats
+    }
+
+    /// Returns the total number of events.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of events at `level`.
+    pub fn level(&self, level: Level) -> usize {
+        self.levels.get(&level).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of events with the target `target`.
+    pub fn target(&self, target: &str) -> usize {
+        self.targets.get(target).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of events per level, from most to least severe.
+    ///
+    /// Levels without any events are left out.
+    pub fn levels(&self) -> &BTreeMap<Level, usize> {
+        &self.levels
+    }
+
+    /// Returns the number of events per target, sorted by target.
+    pub fn targets(&self) -> &BTreeMap<String, usize> {
+        &self.targets
+    }
+}
+
+/// Formats as `N records; levels: LEVEL=n ...; targets: target=n ...`.
+impl fmt::Display for LogStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} records", self.total)?;
+        if self.total == 0 {
+            return Ok(());
+        }
+        f.write_str("; levels:")?;
+        for (level, count) in &self.levels {
+            write!(f, " {}={}", level, count)?;
+        }
+        f.write_str("; targets:")?;
+        for (target, count) in &self.targets {
+            write!(f, " {}={}", target, count)?;
+        }
+        Ok(())
+    }
+}
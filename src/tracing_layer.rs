@@ -0,0 +1,121 @@
+use crate::{truncate_args, Capture, Record, Value, ACTIVE};
+use log::Level;
+use std::fmt;
+use std::sync::atomic::Ordering;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+
+/// A `tracing_subscriber` layer that captures `tracing` events into the same
+/// queue as `log` records.
+///
+/// The `message` field becomes the record's message, and all other fields
+/// become key-value pairs. Events are only captured while a [`Logger`] handle
+/// is alive, and are filtered like `log` records.
+///
+/// Use [`Builder::tracing`] to install this layer as the global default
+/// subscriber, or add it to a subscriber of your own.
+///
+/// [`Logger`]: crate::Logger
+/// [`Builder::tracing`]: crate::Builder::tracing
+///
+/// # Examples
+///
+/// ```
+/// use logtest::{Logger, TracingLayer};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let logger = Logger::start();
+/// let subscriber = tracing_subscriber::registry().with(TracingLayer);
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::warn!(retries = 3, "request failed");
+/// });
+///
+/// let record = logger.pop_front().unwrap();
+/// assert_eq!(record.args(), "request failed");
+/// assert_eq!(record.key_value("retries").unwrap().as_u64(), Some(3));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingLayer;
+
+impl<S: Subscriber> tracing_subscriber::Layer<S> for TracingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if ACTIVE.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let meta = event.metadata();
+        let level = match *meta.level() {
+            tracing::Level::ERROR => Level::Error,
+            tracing::Level::WARN => Level::Warn,
+            tracing::Level::INFO => Level::Info,
+            tracing::Level::DEBUG => Level::Debug,
+            tracing::Level::TRACE => Level::Trace,
+        };
+        let metadata = log::Metadata::builder()
+            .level(level)
+            .target(meta.target())
+            .build();
+        let capture = match Capture::load(&metadata) {
+            Some(capture) => capture,
+            None => return,
+        };
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let mut args = visitor.message.unwrap_or_default();
+        let truncated = truncate_args(&mut args, capture.max_args_len);
+        let mut record = Record::from_parts(args, level, meta.target().to_owned(), visitor.pairs);
+        record.truncated = truncated;
+        record.module_path = meta.module_path().map(ToOwned::to_owned);
+        record.file = meta.file().map(ToOwned::to_owned);
+        record.line = meta.line();
+        capture.push(record);
+    }
+}
+
+/// A `tracing` field visitor.
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    pairs: Vec<(String, Value)>,
+}
+
+impl FieldVisitor {
+    fn push(&mut self, field: &Field, value: Value) {
+        self.pairs.push((field.name().to_owned(), value));
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+        } else {
+            self.push(field, Value::Debug(format!("{:?}", value)));
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_owned());
+        } else {
+            self.push(field, Value::String(value.to_owned()));
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, Value::I64(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.push(field, Value::U64(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, Value::F64(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, Value::Bool(value));
+    }
+}
@@ -0,0 +1,16 @@
+use logtest::Logger;
+
+#[test]
+fn args_eq() {
+    let logger = Logger::start();
+    log::info!("hello");
+    log::info!("hello {}", "world");
+
+    let record = logger.pop_front().unwrap();
+    assert!(record.args_eq("hello"));
+    assert_eq!(record, "hello");
+
+    let record = logger.pop_front().unwrap();
+    assert!(record.args_eq("hello world"));
+    assert!(!record.args_eq("hello"));
+}
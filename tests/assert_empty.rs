@@ -0,0 +1,23 @@
+use logtest::Logger;
+use std::panic;
+
+#[test]
+fn assert_empty() {
+    let logger = Logger::start();
+    logger.assert_empty();
+
+    log::info!("expected");
+    let _ = logger.pop_front();
+    logger.assert_empty();
+
+    log::warn!("unexpected");
+    log::error!("also unexpected");
+    let err = panic::catch_unwind(|| logger.assert_empty()).unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(msg.starts_with("expected no records, found 2:"));
+    assert!(msg.contains("[WARN assert_empty] unexpected"));
+    assert!(msg.contains("[ERROR assert_empty] also unexpected"));
+
+    // The records are left in place.
+    assert_eq!(logger.len(), 2);
+}
@@ -0,0 +1,35 @@
+use logtest::{assert_kv, Logger};
+use std::panic::{self, AssertUnwindSafe};
+
+fn panic_message(f: impl FnOnce()) -> String {
+    let err = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+    err.downcast::<String>().map(|s| *s).unwrap()
+}
+
+#[test]
+fn assert_kv() {
+    let logger = Logger::start();
+    log::info!(color = "blue", retries = 3, ok = true; "hello");
+
+    let record = logger.pop_front().unwrap();
+    assert_kv!(record, "color" => "blue");
+    assert_kv!(record, "retries" => 3);
+    assert_kv!(record, "retries" => "3");
+    assert_kv!(record, "ok" => true);
+
+    let items = [1, 2, 3];
+    log::info!(len = items.len(), initial = 'x'; "sized");
+    let sized = logger.pop_front().unwrap();
+    assert_kv!(sized, "len" => items.len());
+    assert_kv!(sized, "len" => 3u8);
+    assert_kv!(sized, "initial" => 'x');
+
+    let message = panic_message(|| assert_kv!(record, "color" => "red"));
+    assert_eq!(message, "expected `color` to be `red`, found `blue`");
+
+    let message = panic_message(|| assert_kv!(record, "size" => 1));
+    assert_eq!(
+        message,
+        "expected key `size` to be logged, found keys [color, retries, ok]"
+    );
+}
@@ -0,0 +1,34 @@
+use log::Level;
+use logtest::{assert_logged, Logger};
+use std::panic::{self, AssertUnwindSafe};
+
+fn panic_message(f: impl FnOnce()) -> String {
+    let err = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+    err.downcast::<String>().map(|s| *s).unwrap()
+}
+
+#[test]
+fn assert_logged() {
+    let logger = Logger::start();
+    log::info!("hello");
+    log::warn!("world");
+    assert_logged!(logger, "hello");
+    assert_logged!(logger, Level::Warn, "world");
+
+    log::info!("unexpected");
+    let message = panic_message(|| assert_logged!(logger, "expected"));
+    assert_eq!(
+        message,
+        "expected `expected` to be logged, found `[INFO assert_logged] unexpected`"
+    );
+
+    log::info!("wrong level");
+    let message = panic_message(|| assert_logged!(logger, Level::Error, "wrong level"));
+    assert_eq!(
+        message,
+        "expected `[ERROR] wrong level` to be logged, found `[INFO assert_logged] wrong level`"
+    );
+
+    let message = panic_message(|| assert_logged!(logger, "missing"));
+    assert_eq!(message, "expected `missing` to be logged, found no records");
+}
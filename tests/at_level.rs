@@ -0,0 +1,41 @@
+use log::Level;
+use logtest::Logger;
+
+#[test]
+fn at_level() {
+    let logger = Logger::start();
+    log::info!("info");
+    log::warn!("warn");
+    log::error!("error");
+    assert_eq!(logger.errors().count(), 1);
+    assert_eq!(logger.len(), 2);
+    assert_eq!(logger.pop_front().unwrap().args(), "info");
+    assert_eq!(logger.pop_front().unwrap().args(), "warn");
+
+    log::warn!("first");
+    log::error!("error");
+    log::warn!("second");
+    let warnings: Vec<_> = logger.warnings().map(|r| r.args().to_owned()).collect();
+    assert_eq!(warnings, ["first", "second"]);
+    assert_eq!(logger.pop_front().unwrap().args(), "error");
+
+    log::debug!("debug");
+    log::trace!("trace");
+    let debug: Vec<_> = logger.at_level(Level::Debug).collect();
+    assert_eq!(debug.len(), 1);
+    assert_eq!(debug[0].args(), "debug");
+    assert_eq!(logger.pop_front().unwrap().args(), "trace");
+
+    log::trace!("trace");
+    log::info!("info");
+    log::error!("error");
+    log::warn!("warn");
+    let severe: Vec<_> = logger
+        .at_least(Level::Warn)
+        .map(|r| r.args().to_owned())
+        .collect();
+    assert_eq!(severe, ["error", "warn"]);
+    assert_eq!(logger.pop_front().unwrap().args(), "trace");
+    assert_eq!(logger.pop_front().unwrap().args(), "info");
+    assert!(logger.is_empty());
+}
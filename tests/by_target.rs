@@ -0,0 +1,26 @@
+use logtest::{Logger, Record};
+
+#[test]
+fn by_target() {
+    let logger = Logger::start();
+    log::info!(target: "a", "a1");
+    log::info!(target: "b", "b1");
+    log::info!(target: "a", "a2");
+    log::info!(target: "b", "b2");
+    log::info!(target: "a", "a3");
+
+    let args = |records: &[Record]| -> Vec<String> {
+        records.iter().map(|r| r.args().to_owned()).collect()
+    };
+
+    let groups = logger.records_by_target();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(logger.len(), 5);
+
+    let drained = logger.by_target();
+    assert_eq!(drained, groups);
+    assert_eq!(args(&drained["a"]), ["a1", "a2", "a3"]);
+    assert_eq!(args(&drained["b"]), ["b1", "b2"]);
+    assert!(logger.is_empty());
+    assert!(logger.by_target().is_empty());
+}
@@ -0,0 +1,26 @@
+use logtest::{Logger, OverflowPolicy};
+
+#[test]
+fn capacity() {
+    let logger = Logger::start_with_capacity(2);
+    for i in 0..4 {
+        log::info!("message {}", i);
+    }
+    assert_eq!(logger.len(), 2);
+    assert_eq!(logger.dropped_count(), 2);
+    assert_eq!(logger.pop_front().unwrap().args(), "message 2");
+    assert_eq!(logger.pop_front().unwrap().args(), "message 3");
+
+    let logger = Logger::builder()
+        .capacity(2)
+        .overflow(OverflowPolicy::DropNewest)
+        .start();
+    assert_eq!(logger.dropped_count(), 0);
+    for i in 0..4 {
+        log::info!("message {}", i);
+    }
+    assert_eq!(logger.len(), 2);
+    assert_eq!(logger.dropped_count(), 2);
+    assert_eq!(logger.pop_front().unwrap().args(), "message 0");
+    assert_eq!(logger.pop_front().unwrap().args(), "message 1");
+}
@@ -0,0 +1,18 @@
+use logtest::Logger;
+
+#[test]
+fn capture_scope() {
+    let mut logger = Logger::start();
+    log::info!("noise");
+
+    let scope = logger.capture_scope();
+    assert!(scope.is_empty());
+    log::info!("target");
+    assert_eq!(scope.len(), 1);
+    assert!(scope.contains_args("target"));
+    drop(scope);
+
+    assert!(logger.is_empty());
+    log::info!("more noise");
+    assert_eq!(logger.pop_front().unwrap().args(), "more noise");
+}
@@ -0,0 +1,37 @@
+use logtest::Logger;
+
+fn args(records: Vec<logtest::Record>) -> Vec<String> {
+    records.iter().map(|r| r.args().to_owned()).collect()
+}
+
+#[test]
+fn checkpoint() {
+    let logger = Logger::start();
+    log::info!("fixture");
+    let setup = logger.checkpoint();
+    log::info!("first phase");
+    let first = logger.checkpoint();
+    log::info!("second phase");
+
+    assert_eq!(args(logger.since(&setup)), ["first phase", "second phase"]);
+    assert_eq!(args(logger.since(&first)), ["second phase"]);
+
+    // Popping events doesn't shift the checkpoints.
+    assert_eq!(logger.pop_front().unwrap().args(), "fixture");
+    assert_eq!(logger.pop_front().unwrap().args(), "first phase");
+    assert_eq!(args(logger.since(&setup)), ["second phase"]);
+    assert_eq!(args(logger.since(&first)), ["second phase"]);
+
+    let last = logger.checkpoint();
+    assert!(logger.since(&last).is_empty());
+    assert!(setup < first && first < last);
+
+    // Building a record doesn't use up a sequence number.
+    let built = logtest::Record::builder().args("expected").build();
+    assert_eq!(built.seq(), 0);
+    assert_eq!(logger.checkpoint(), last);
+
+    // Captured records never have sequence number 0.
+    log::info!("captured");
+    assert!(logger.pop_back().unwrap().seq() > 0);
+}
@@ -0,0 +1,16 @@
+use logtest::Logger;
+
+#[test]
+fn clear() {
+    let logger = Logger::start();
+    log::info!("one");
+    log::info!("two");
+    log::info!("three");
+    assert_eq!(logger.clear(), 3);
+    assert!(logger.is_empty());
+    assert_eq!(logger.clear(), 0);
+
+    log::info!("fresh");
+    assert_eq!(logger.len(), 1);
+    assert_eq!(logger.pop_front().unwrap().args(), "fresh");
+}
@@ -0,0 +1,15 @@
+use logtest::Logger;
+
+#[test]
+fn clone() {
+    let logger = Logger::start();
+    kv_log_macro::info!("hello", { color: "blue" });
+
+    let record = logger.pop_front().unwrap();
+    let saved = record.clone();
+    assert_eq!(saved, record);
+    assert_eq!(saved.seq(), record.seq());
+    assert_eq!(saved.timestamp(), record.timestamp());
+    assert_eq!(saved.thread_id(), record.thread_id());
+    assert_eq!(saved.line(), record.line());
+}
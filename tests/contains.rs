@@ -0,0 +1,17 @@
+use log::Level;
+use logtest::Logger;
+
+#[test]
+fn contains() {
+    let logger = Logger::start();
+    log::info!("hello");
+    log::warn!("world");
+    log::info!("user 42 connected");
+
+    assert!(logger.contains_args("world"));
+    assert!(logger.contains_args("42"));
+    assert!(!logger.contains_args("missing"));
+    assert!(logger.contains(|r| r.level() == Level::Warn));
+    assert!(!logger.contains(|r| r.level() == Level::Error));
+    assert_eq!(logger.len(), 3);
+}
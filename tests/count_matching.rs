@@ -0,0 +1,18 @@
+use logtest::Logger;
+
+#[test]
+fn count_matching() {
+    let logger = Logger::start();
+    log::info!("cache miss for user 1");
+    log::info!("loading user 1");
+    log::warn!("cache miss for user 2");
+    log::info!("cache hit for user 1");
+    log::error!("database unavailable");
+
+    assert_eq!(logger.count_matching(|r| r.args().contains("cache")), 3);
+    assert_eq!(logger.count_matching(|r| r.args().contains("miss")), 2);
+    assert_eq!(logger.count_matching(|r| r.args().contains("timeout")), 0);
+
+    // Nothing is drained.
+    assert_eq!(logger.len(), 5);
+}
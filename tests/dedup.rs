@@ -0,0 +1,40 @@
+use logtest::Logger;
+
+#[test]
+fn dedup() {
+    let logger = Logger::start();
+    log::info!("connecting");
+    for attempt in 0..50 {
+        log::warn!(attempt = attempt; "connection refused, retrying");
+    }
+    for _ in 0..2 {
+        log::warn!("connection refused, retrying");
+    }
+    log::info!("connected");
+    log::info!("connected");
+
+    // Differing key-value pairs prevent collapsing.
+    let runs = logger.dedup();
+    assert_eq!(runs.len(), 1 + 50 + 1 + 1);
+    let counts: Vec<_> = runs.iter().map(|(_, count)| *count).collect();
+    assert_eq!(&counts[..2], [1, 1]);
+    assert_eq!(&counts[counts.len() - 2..], [2, 2]);
+
+    // Unless they're ignored.
+    let runs = logger.dedup_by(|a, b| {
+        a.args() == b.args() && a.level() == b.level() && a.target() == b.target()
+    });
+    let summary: Vec<_> = runs.iter().map(|(r, count)| (r.args(), *count)).collect();
+    assert_eq!(
+        summary,
+        [
+            ("connecting", 1),
+            ("connection refused, retrying", 52),
+            ("connected", 2),
+        ]
+    );
+    assert_eq!(runs[1].0.key_value("attempt").unwrap().as_i64(), Some(0));
+
+    // Nothing is removed.
+    assert_eq!(logger.len(), 55);
+}
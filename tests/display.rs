@@ -0,0 +1,30 @@
+use logtest::Logger;
+
+#[test]
+fn display() {
+    let logger = Logger::start();
+
+    log::info!(target: "my_crate::db", "connected to 127.0.0.1:5432");
+    assert_eq!(
+        logger.pop_front().unwrap().to_string(),
+        "[INFO my_crate::db] connected to 127.0.0.1:5432"
+    );
+
+    log::warn!(target: "my_crate::db", attempt = 1; "reconnecting");
+    assert_eq!(
+        logger.pop_front().unwrap().to_string(),
+        "[WARN my_crate::db] reconnecting {attempt=1}"
+    );
+
+    log::info!(target: "my_crate", color = "blue"; "hello");
+    assert_eq!(
+        logger.pop_front().unwrap().to_string(),
+        r#"[INFO my_crate] hello {color="blue"}"#
+    );
+
+    kv_log_macro::error!("failed", { peer: "a", retry: true, ratio: 0.5 });
+    assert_eq!(
+        logger.pop_front().unwrap().to_string(),
+        r#"[ERROR display] failed {peer="a" retry=true ratio=0.5}"#
+    );
+}
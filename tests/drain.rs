@@ -0,0 +1,35 @@
+use logtest::Logger;
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn drain() {
+    let logger = Logger::start();
+    for i in 0..10 {
+        log::info!("message {}", i);
+    }
+
+    let records = logger.drain(3..7);
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0].args(), "message 3");
+    assert_eq!(records[3].args(), "message 6");
+    assert_eq!(logger.len(), 6);
+
+    let records = logger.drain(..);
+    assert_eq!(records.len(), 6);
+    assert_eq!(records[0].args(), "message 0");
+    assert_eq!(records[2].args(), "message 2");
+    assert_eq!(records[3].args(), "message 7");
+    assert!(logger.drain(..).is_empty());
+
+    log::info!("message");
+    let result = panic::catch_unwind(AssertUnwindSafe(|| logger.drain(0..2)));
+    assert!(result.is_err());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| logger.drain(1..=1)));
+    assert!(result.is_err());
+
+    // The queue is still usable afterwards.
+    log::info!("after");
+    assert_eq!(logger.len(), 2);
+    assert_eq!(logger.drain(1..=1)[0].args(), "after");
+    assert!(Logger::start().is_empty());
+}
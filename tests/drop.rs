@@ -0,0 +1,19 @@
+use log::LevelFilter;
+use logtest::Logger;
+
+#[test]
+fn drop() {
+    log::set_max_level(LevelFilter::Warn);
+
+    let logger = Logger::start();
+    assert_eq!(log::max_level(), LevelFilter::Trace);
+    std::mem::drop(logger);
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+
+    let first = Logger::start();
+    let second = Logger::start();
+    std::mem::drop(second);
+    assert_eq!(log::max_level(), LevelFilter::Trace);
+    std::mem::drop(first);
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+}
@@ -0,0 +1,20 @@
+use logtest::Logger;
+
+#[test]
+fn dump() {
+    let logger = Logger::start();
+    assert_eq!(logger.dump(), "");
+
+    log::warn!(target: "my_crate", "low disk space");
+    log::info!(target: "my_crate::gc", path = "/tmp", freed = 12; "cleaned up");
+    log::error!(target: "hyper", "connection reset");
+
+    let expected = "0: [WARN my_crate] low disk space\n\
+                    1: [INFO my_crate::gc] cleaned up {path=\"/tmp\" freed=12}\n\
+                    2: [ERROR hyper] connection reset";
+    assert_eq!(logger.dump(), expected);
+
+    // Dumping doesn't consume anything, and is stable.
+    assert_eq!(logger.len(), 3);
+    assert_eq!(logger.dump(), expected);
+}
@@ -0,0 +1,51 @@
+use logtest::Logger;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+struct NestedError {
+    message: &'static str,
+    source: Option<Box<NestedError>>,
+}
+
+impl fmt::Display for NestedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+impl Error for NestedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|err| err as &(dyn Error + 'static))
+    }
+}
+
+#[test]
+fn error_chain() {
+    let logger = Logger::start();
+
+    let err = NestedError {
+        message: "request failed",
+        source: Some(Box::new(NestedError {
+            message: "connection reset",
+            source: Some(Box::new(NestedError {
+                message: "broken pipe",
+                source: None,
+            })),
+        })),
+    };
+    log::error!(err:err = err; "giving up");
+    let record = logger.pop_front().unwrap();
+    assert_eq!(
+        record.error_chain().unwrap(),
+        ["request failed", "connection reset", "broken pipe"]
+    );
+    assert_eq!(record.key_value("err").unwrap(), "request failed");
+
+    log::info!(retries = 3; "retrying");
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record.error_chain(), None);
+    assert_eq!(record.key_value("retries").unwrap(), "3");
+}
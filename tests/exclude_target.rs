@@ -0,0 +1,23 @@
+use logtest::Logger;
+
+#[test]
+fn exclude_target() {
+    let logger = Logger::start_excluding(&["noisy", "hyper"]);
+    log::info!(target: "noisy", "ignored");
+    log::warn!(target: "noisy::inner", "ignored");
+    log::info!(target: "hyper", "ignored");
+    log::info!(target: "my_crate", "captured");
+    assert_eq!(logger.len(), 1);
+    assert_eq!(logger.pop_front().unwrap().target(), "my_crate");
+
+    // Exclusions take precedence over allowed targets.
+    let logger = Logger::builder()
+        .target("my_crate")
+        .exclude_target("my_crate::db")
+        .start();
+    log::info!(target: "my_crate::db", "ignored");
+    log::info!(target: "my_crate::http", "captured");
+    log::info!(target: "noisy", "ignored");
+    assert_eq!(logger.pop_front().unwrap().target(), "my_crate::http");
+    assert!(logger.is_empty());
+}
@@ -0,0 +1,20 @@
+use logtest::Logger;
+
+#[test]
+fn extract() {
+    let logger = Logger::start();
+    for i in 0..6 {
+        log::info!(target: if i % 2 == 0 { "db" } else { "http" }, "message {}", i);
+    }
+
+    let args = |records: Vec<logtest::Record>| -> Vec<String> {
+        records.iter().map(|r| r.args().to_owned()).collect()
+    };
+    let db = logger.extract(|r| r.target() == "db");
+    assert_eq!(args(db), ["message 0", "message 2", "message 4"]);
+    assert_eq!(
+        args(logger.drain(..)),
+        ["message 1", "message 3", "message 5"]
+    );
+    assert!(logger.extract(|_| true).is_empty());
+}
@@ -0,0 +1,34 @@
+use logtest::Logger;
+
+#[test]
+fn find_by_kv() {
+    let logger = Logger::start();
+    kv_log_macro::info!("started", { request_id: "a" });
+    kv_log_macro::info!("started", { request_id: "b" });
+    kv_log_macro::info!("finished", { request_id: "a" });
+    kv_log_macro::info!("finished", { request_id: "b" });
+
+    let record = logger.find_by_kv("request_id", "b").unwrap();
+    assert_eq!(record.args(), "started");
+    assert_eq!(record.key_value("request_id").unwrap(), "b");
+
+    assert!(logger.find_by_kv("request_id", "c").is_none());
+    assert!(logger.find_by_kv("missing", "b").is_none());
+
+    let remaining: Vec<_> = logger
+        .map(|r| {
+            (
+                r.args().to_owned(),
+                r.key_value("request_id").unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        remaining,
+        vec![
+            ("started".to_owned(), "a".to_owned()),
+            ("finished".to_owned(), "a".to_owned()),
+            ("finished".to_owned(), "b".to_owned()),
+        ]
+    );
+}
@@ -0,0 +1,21 @@
+use logtest::Logger;
+
+#[test]
+fn finish() {
+    let logger = Logger::start();
+    log::info!("hello");
+    log::info!("world");
+
+    let records = logger.finish();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].args(), "hello");
+    assert_eq!(records[1].args(), "world");
+
+    log::info!("discarded");
+    let logger = Logger::start();
+    assert!(logger.is_empty());
+
+    // Starting a new logger captures again.
+    log::info!("captured");
+    assert_eq!(logger.pop_front().unwrap().args(), "captured");
+}
@@ -0,0 +1,33 @@
+use logtest::Logger;
+
+#[test]
+fn flush_to_writer() {
+    let logger = Logger::start();
+    log::info!(target: "app", "starting");
+    log::error!(target: "app::db", code = 3; "connection lost");
+
+    let mut out = Vec::new();
+    logger.flush_to_writer(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(
+        out,
+        "[INFO app] starting\n[ERROR app::db] connection lost {code=3}\n"
+    );
+    assert!(logger.is_empty());
+
+    // Trait objects work as well.
+    log::warn!(target: "app", "retrying");
+    let mut out: Box<dyn std::io::Write> = Box::new(Vec::new());
+    logger.flush_to_writer(&mut *out).unwrap();
+    let mut buf = Vec::new();
+    log::warn!(target: "app", "again");
+    logger
+        .flush_to_writer(&mut buf as &mut dyn std::io::Write)
+        .unwrap();
+    assert_eq!(buf, b"[WARN app] again\n");
+
+    // An empty queue writes nothing.
+    let mut out = Vec::new();
+    logger.flush_to_writer(&mut out).unwrap();
+    assert!(out.is_empty());
+}
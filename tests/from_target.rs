@@ -0,0 +1,21 @@
+use logtest::Logger;
+
+#[test]
+fn from_target() {
+    let logger = Logger::start();
+    log::info!(target: "my_crate", "root");
+    log::info!(target: "hyper", "noise");
+    log::info!(target: "my_crate_extras", "extras");
+    log::info!(target: "my_crate::db", "db");
+    log::info!(target: "my_crate::db::pool", "pool");
+
+    let args: Vec<_> = logger
+        .from_target("my_crate")
+        .map(|r| r.args().to_owned())
+        .collect();
+    assert_eq!(args, ["root", "db", "pool"]);
+
+    assert_eq!(logger.pop_front().unwrap().args(), "noise");
+    assert_eq!(logger.pop_front().unwrap().args(), "extras");
+    assert!(logger.is_empty());
+}
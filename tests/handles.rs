@@ -0,0 +1,27 @@
+use log::LevelFilter;
+use logtest::Logger;
+
+#[test]
+fn handles() {
+    log::set_max_level(LevelFilter::Off);
+    let first = Logger::start();
+    let second = first.clone();
+    for i in 0..6 {
+        log::info!("message {}", i);
+    }
+
+    let mut seen = Vec::new();
+    for i in 0..6 {
+        let handle = if i % 2 == 0 { &first } else { &second };
+        seen.push(handle.pop_front().unwrap().args().to_owned());
+    }
+    let expected: Vec<_> = (0..6).map(|i| format!("message {}", i)).collect();
+    assert_eq!(seen, expected);
+    assert!(first.is_empty() && second.is_empty());
+
+    // Only the original handle restores the max level.
+    drop(second);
+    assert_eq!(log::max_level(), LevelFilter::Trace);
+    drop(first);
+    assert_eq!(log::max_level(), LevelFilter::Off);
+}
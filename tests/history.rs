@@ -0,0 +1,48 @@
+use logtest::{Logger, Record};
+
+fn args(records: &[Record]) -> Vec<&str> {
+    records.iter().map(|r| r.args()).collect()
+}
+
+#[test]
+fn history() {
+    let logger = Logger::builder().history().start();
+    log::info!("one");
+    log::info!("two");
+    log::info!("three");
+    log::info!("four");
+
+    assert_eq!(logger.pop_front().unwrap().args(), "one");
+    logger.retain(|r| r.args() != "three");
+    assert_eq!(args(&logger.history()), ["one", "three"]);
+    assert_eq!(args(&logger.records()), ["two", "four"]);
+
+    // Nothing is lost between the history and the live queue.
+    let seen: Vec<_> = logger
+        .history()
+        .into_iter()
+        .chain(logger.records())
+        .collect();
+    assert_eq!(seen.len(), 4);
+    assert!(!seen.iter().any(|r| r.args().contains("secret")));
+
+    logger.clear();
+    logger.clear_history();
+    assert!(logger.history().is_empty());
+    drop(logger);
+
+    // The history is capped.
+    let logger = Logger::builder().history_limit(2).start();
+    log::info!("a");
+    log::info!("b");
+    log::info!("c");
+    assert_eq!(logger.clear(), 3);
+    assert_eq!(args(&logger.history()), ["b", "c"]);
+    drop(logger);
+
+    // Without history, nothing is kept.
+    let logger = Logger::start();
+    log::info!("gone");
+    assert_eq!(logger.pop_front().unwrap().args(), "gone");
+    assert!(logger.history().is_empty());
+}
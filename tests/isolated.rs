@@ -0,0 +1,60 @@
+use logtest::{Logger, RecordMatcher};
+use std::thread;
+use std::time::Duration;
+
+// Unlike the other test files, these tests run in parallel on purpose.
+
+#[test]
+fn isolated() {
+    let mut logger = Logger::start_isolated();
+    for _ in 0..100 {
+        log::info!("first");
+    }
+    assert_eq!(logger.len(), 100);
+    assert!(logger.all(&RecordMatcher::new().args_eq("first")));
+}
+
+#[test]
+fn isolated_parallel() {
+    let mut logger = Logger::start_isolated();
+    for _ in 0..100 {
+        log::info!("second");
+    }
+    assert_eq!(logger.len(), 100);
+    assert!(logger.all(&RecordMatcher::new().args_eq("second")));
+}
+
+#[test]
+fn isolated_cross_thread() {
+    let logger = Logger::start_isolated();
+    let global = Logger::start();
+    log::info!("local");
+    thread::spawn(|| log::info!("remote")).join().unwrap();
+    assert_eq!(logger.pop_front().unwrap().args(), "local");
+    assert!(logger.is_empty());
+
+    // The spawned thread's event went to the global queue.
+    assert!(global.contains_args("remote"));
+
+    // Once dropped, this thread's events go to the global queue again.
+    drop(logger);
+    log::info!("after");
+    assert!(global.contains_args("after"));
+}
+
+#[test]
+fn isolated_elapsed() {
+    let logger = Logger::start_isolated();
+    log::info!("a");
+    thread::sleep(Duration::from_millis(20));
+
+    // Starting another logger doesn't reset this logger's elapsed baseline.
+    thread::spawn(|| drop(Logger::start_isolated()))
+        .join()
+        .unwrap();
+    log::info!("b");
+
+    let a = logger.pop_front().unwrap();
+    let b = logger.pop_front().unwrap();
+    assert!(b.elapsed() >= a.elapsed() + Duration::from_millis(20));
+}
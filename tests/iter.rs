@@ -0,0 +1,17 @@
+use logtest::Logger;
+
+#[test]
+fn iter() {
+    let logger = Logger::start();
+    log::info!("one");
+    log::info!("two");
+    log::info!("three");
+
+    assert_eq!(logger.iter().count(), 3);
+    let args: Vec<_> = logger.iter().map(|r| r.args().to_owned()).collect();
+    assert_eq!(args, ["one", "two", "three"]);
+
+    assert_eq!(logger.pop_front().unwrap().args(), "one");
+    assert_eq!(logger.pop_front().unwrap().args(), "two");
+    assert_eq!(logger.pop_front().unwrap().args(), "three");
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "json")]
+
+use logtest::{Logger, Record};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct User {
+    id: u64,
+    tags: Vec<&'static str>,
+}
+
+#[test]
+fn json() {
+    let logger = Logger::start();
+
+    kv_log_macro::info!("typed", { retries: 3, ok: true, name: "chashu", ratio: 0.5 });
+    let record = logger.pop_front().unwrap();
+    assert_eq!(
+        serde_json::Value::Object(record.kv_json()),
+        json!({ "retries": 3, "ok": true, "name": "chashu", "ratio": 0.5 })
+    );
+
+    let user = User {
+        id: 7,
+        tags: vec!["admin", "beta"],
+    };
+    log::info!(user:serde = user; "nested");
+    let record = logger.pop_front().unwrap();
+    assert_eq!(
+        serde_json::Value::Object(record.kv_json()),
+        json!({ "user": { "id": 7, "tags": ["admin", "beta"] } })
+    );
+
+    log::info!(peer = "a", peer = "b"; "duplicate");
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record.key_value("peer").unwrap(), "a");
+    assert_eq!(record.kv_json()["peer"], "a");
+
+    let built = Record::builder()
+        .key_value("count", 2)
+        .key_value("count", 3)
+        .build();
+    assert_eq!(
+        serde_json::Value::Object(built.kv_json()),
+        json!({ "count": 2 })
+    );
+}
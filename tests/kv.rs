@@ -0,0 +1,70 @@
+use logtest::{Logger, Value};
+
+#[test]
+fn kv() {
+    let logger = Logger::start();
+
+    kv_log_macro::info!("request", { method: "GET", path: "/", status: 200 });
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(
+        msg.key_values(),
+        vec![
+            ("method".to_owned(), Value::String("GET".to_owned())),
+            ("path".to_owned(), Value::String("/".to_owned())),
+            ("status".to_owned(), Value::I64(200)),
+        ]
+    );
+    assert_eq!(msg.key_value("method").unwrap(), "GET");
+    assert_eq!(msg.key_value("status"), Some(&Value::I64(200)));
+    assert_eq!(msg.key_value("status").unwrap(), "200");
+    assert_eq!(msg.key_value("missing"), None);
+
+    kv_log_macro::info!("typed", { retries: 3, ok: true, ratio: 0.5, name: "chashu" });
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(msg.key_value("retries").unwrap().as_u64(), Some(3));
+    assert_eq!(msg.key_value("ok").unwrap().as_bool(), Some(true));
+    assert_eq!(msg.key_value("ratio").unwrap().as_f64(), Some(0.5));
+    assert_eq!(msg.key_value("name").unwrap().as_str(), Some("chashu"));
+    assert_eq!(msg.get::<u64>("retries"), Some(3));
+    assert_eq!(msg.get::<bool>("ok"), Some(true));
+    assert_eq!(msg.get::<String>("name"), Some("chashu".to_owned()));
+    assert_eq!(msg.get::<u64>("name"), None);
+    assert_eq!(msg.get::<u64>("missing"), None);
+    assert!(msg.get_parsed::<u64>("name").is_err());
+    assert_eq!(msg.get_parsed::<u64>("missing"), Ok(None));
+
+    log::info!(peer = "a", peer = "b"; "duplicates");
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(
+        msg.key_values(),
+        vec![
+            ("peer".to_owned(), Value::String("a".to_owned())),
+            ("peer".to_owned(), Value::String("b".to_owned())),
+        ]
+    );
+    assert_eq!(msg.key_value("peer").unwrap(), "a");
+
+    let owned = String::from("blue");
+    log::info!(color = owned.as_str(), shade = 'b'; "strings");
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(msg.key_value("color").unwrap(), "blue");
+    assert_eq!(msg.key_value("color").unwrap().to_string(), "blue");
+    assert_eq!(msg.key_value("shade").unwrap(), "b");
+
+    log::info!(list:? = vec![1, 2]; "debug");
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(
+        msg.key_value("list"),
+        Some(&Value::Debug("[1, 2]".to_owned()))
+    );
+
+    assert_eq!(msg.key_value("list").unwrap().to_string(), "[1, 2]");
+
+    let missing: Option<u32> = None;
+    kv_log_macro::info!("types", { count: 3, flag: true, text: "true", missing: missing });
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(msg.key_value("count"), Some(&Value::I64(3)));
+    assert_ne!(msg.key_value("count"), Some(&Value::String("3".to_owned())));
+    assert_ne!(msg.key_value("flag"), msg.key_value("text"));
+    assert!(msg.key_value("missing").unwrap().is_null());
+}
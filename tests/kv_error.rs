@@ -0,0 +1,33 @@
+use log::kv::{self, Source};
+use logtest::Logger;
+
+/// A source that yields one pair, then fails.
+struct Failing;
+
+impl Source for Failing {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn kv::VisitSource<'kvs>) -> Result<(), kv::Error> {
+        visitor.visit_pair(kv::Key::from_str("before"), kv::Value::from(1))?;
+        Err(kv::Error::msg("exotic value"))
+    }
+}
+
+#[test]
+fn kv_error() {
+    let logger = Logger::start();
+    log::logger().log(
+        &log::Record::builder()
+            .args(format_args!("still captured"))
+            .level(log::Level::Info)
+            .target("kv_error")
+            .key_values(&Failing)
+            .build(),
+    );
+
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record.args(), "still captured");
+    assert_eq!(record.kv_error(), Some("exotic value"));
+    assert_eq!(record.key_value("before").unwrap(), "1");
+
+    log::info!(ok = true; "fine");
+    assert_eq!(logger.pop_front().unwrap().kv_error(), None);
+}
@@ -0,0 +1,15 @@
+use logtest::Logger;
+
+fn assert_count(logger: &Logger, expected: usize) {
+    assert_eq!(logger.len(), expected);
+    assert_eq!(logger.is_empty(), expected == 0);
+}
+
+#[test]
+fn len() {
+    let logger = Logger::start();
+    assert_count(&logger, 0);
+    log::info!("hello");
+    log::info!("world");
+    assert_count(&logger, 2);
+}
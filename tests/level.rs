@@ -0,0 +1,17 @@
+use log::LevelFilter;
+use logtest::Logger;
+
+#[test]
+fn level() {
+    let logger = Logger::start_with_level(LevelFilter::Warn);
+    log::info!("ignored");
+    log::warn!("captured");
+    assert_eq!(logger.len(), 1);
+    assert_eq!(logger.pop_front().unwrap().args(), "captured");
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+
+    let logger = Logger::start();
+    assert_eq!(log::max_level(), LevelFilter::Trace);
+    log::info!("captured again");
+    assert_eq!(logger.pop_front().unwrap().args(), "captured again");
+}
@@ -0,0 +1,30 @@
+use log::Level;
+use logtest::Logger;
+
+#[test]
+fn level_counts() {
+    let logger = Logger::start();
+    assert!(logger.level_counts().is_empty());
+
+    log::error!("failed");
+    log::warn!("slow");
+    log::warn!("slower");
+    log::info!("done");
+    log::debug!("details");
+
+    let counts = logger.level_counts();
+    assert_eq!(counts.len(), 4);
+    assert_eq!(counts[&Level::Error], 1);
+    assert_eq!(counts[&Level::Warn], 2);
+    assert_eq!(counts[&Level::Info], 1);
+    assert_eq!(counts[&Level::Debug], 1);
+    assert_eq!(counts.get(&Level::Trace), None);
+    assert_eq!(logger.len(), 5);
+
+    log::trace!("noise");
+    assert_eq!(logger.count_level(Level::Warn), 2);
+    assert_eq!(logger.count_level(Level::Trace), 1);
+    assert_eq!(logger.count_at_least(Level::Error), 1);
+    assert_eq!(logger.count_at_least(Level::Warn), 3);
+    assert_eq!(logger.count_at_least(Level::Trace), logger.len());
+}
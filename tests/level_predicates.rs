@@ -0,0 +1,25 @@
+use log::Level;
+use logtest::Logger;
+
+#[test]
+fn level_predicates() {
+    let logger = Logger::start();
+    log::error!("error");
+    log::warn!("warn");
+    log::info!("info");
+
+    let error = logger.pop_front().unwrap();
+    assert!(error.is_level(Level::Error));
+    assert!(!error.is_level(Level::Warn));
+    assert!(error.at_least(Level::Warn));
+    assert!(error.at_least(Level::Error));
+
+    let warn = logger.pop_front().unwrap();
+    assert!(warn.at_least(Level::Warn));
+    assert!(!warn.at_least(Level::Error));
+
+    let info = logger.pop_front().unwrap();
+    assert!(info.is_level(Level::Info));
+    assert!(!info.at_least(Level::Warn));
+    assert!(info.at_least(Level::Trace));
+}
@@ -0,0 +1,14 @@
+use logtest::Logger;
+
+#[test]
+fn level_str() {
+    let logger = Logger::start();
+    log::error!("e");
+    log::warn!("w");
+    log::info!("i");
+    log::debug!("d");
+    log::trace!("t");
+
+    let levels: Vec<_> = logger.pop_all().iter().map(|r| r.level_str()).collect();
+    assert_eq!(levels, ["error", "warn", "info", "debug", "trace"]);
+}
@@ -0,0 +1,39 @@
+use log::{Level, LevelFilter, Log};
+use logtest::LocalLogger;
+use std::thread;
+
+#[test]
+fn local_logger() {
+    // Separate loggers don't see each other's events.
+    let first = LocalLogger::new();
+    let second = LocalLogger::new();
+    let record = log::Record::builder()
+        .args(format_args!("direct"))
+        .level(Level::Warn)
+        .target("local_logger")
+        .build();
+    first.log(&record);
+    assert_eq!(first.len(), 1);
+    assert!(second.is_empty());
+    let popped = first.pop_front().unwrap();
+    assert_eq!(popped.args(), "direct");
+    assert_eq!(popped.level(), Level::Warn);
+
+    // A clone can be installed globally while the original makes assertions.
+    log::set_boxed_logger(Box::new(first.clone())).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+    log::info!("one");
+    thread::spawn(|| log::info!("two")).join().unwrap();
+    log::info!("three");
+
+    let records = first.records();
+    assert_eq!(records.len(), 3);
+    assert!(records.windows(2).all(|w| w[0].seq() < w[1].seq()));
+    assert_eq!(first.pop_back().unwrap().args(), "three");
+    assert_eq!(first.drain(..).len(), 2);
+    assert!(second.is_empty());
+
+    log::info!("four");
+    assert_eq!(first.peek().unwrap().args(), "four");
+    assert_eq!(first.clear(), 1);
+}
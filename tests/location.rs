@@ -0,0 +1,48 @@
+use logtest::Logger;
+
+mod client {
+    pub(crate) fn connect() {
+        log::info!("connecting");
+    }
+
+    pub(crate) mod audit {
+        pub(crate) fn record() {
+            log::info!(target: "audit", "connected");
+        }
+    }
+}
+
+#[test]
+fn location() {
+    let logger = Logger::start();
+
+    let line = line!();
+    log::info!("hello");
+    let msg = logger.pop_front().unwrap();
+    assert!(msg.file().unwrap().ends_with("tests/location.rs"));
+    assert_eq!(msg.line(), Some(line + 1));
+    assert_eq!(msg.module_path(), Some("location"));
+
+    client::connect();
+    let msg = logger.pop_front().unwrap();
+    assert!(msg.module_path().unwrap().starts_with("location"));
+    assert_eq!(msg.module_path(), Some("location::client"));
+    assert_eq!(msg.target(), "location::client");
+
+    client::audit::record();
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(msg.target(), "audit");
+    assert_eq!(msg.module_path(), Some("location::client::audit"));
+
+    log::logger().log(
+        &log::Record::builder()
+            .args(format_args!("manual"))
+            .level(log::Level::Info)
+            .build(),
+    );
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(msg.args(), "manual");
+    assert_eq!(msg.file(), None);
+    assert_eq!(msg.line(), None);
+    assert_eq!(msg.module_path(), None);
+}
@@ -0,0 +1,37 @@
+use log::Level;
+use logtest::Logger;
+use std::panic;
+
+#[test]
+fn lock() {
+    let logger = Logger::start();
+    for i in 0..300 {
+        if i % 3 == 0 {
+            log::warn!("warning {}", i);
+        } else {
+            log::info!("info {}", i);
+        }
+    }
+
+    let records = logger.lock();
+    assert_eq!(records.len(), 300);
+    let warnings = records.iter().filter(|r| r.level() == Level::Warn).count();
+    assert_eq!(warnings, 100);
+    assert_eq!(records.get(3).unwrap().args(), "warning 3");
+    let mut infos = 0;
+    for record in &records {
+        if record.level() == Level::Info {
+            infos += 1;
+        }
+    }
+    assert_eq!(infos, 200);
+    drop(records);
+    assert_eq!(logger.records().len(), 300);
+
+    // Logging while the guard is held panics instead of deadlocking.
+    let result = panic::catch_unwind(|| {
+        let _records = logger.lock();
+        log::info!("reentrant");
+    });
+    assert!(result.is_err());
+}
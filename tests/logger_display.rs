@@ -0,0 +1,43 @@
+use logtest::Logger;
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn logger_display() {
+    let logger = Logger::start();
+    assert_eq!(logger.to_string(), "Logger { empty }");
+
+    log::info!(target: "my_crate", "one");
+    assert_eq!(
+        logger.to_string(),
+        "Logger { 1 records; levels: INFO=1; targets: my_crate=1 }"
+    );
+
+    log::info!(target: "my_crate", "two");
+    log::warn!(target: "hyper", "three");
+    log::error!(target: "my_crate::db", "four");
+    log::info!(target: "hyper", "five");
+    assert_eq!(
+        logger.to_string(),
+        "Logger { 5 records; levels: ERROR=1 WARN=1 INFO=3; targets: hyper=2 my_crate=2 my_crate::db=1 }"
+    );
+
+    // Formatting doesn't consume anything.
+    assert_eq!(logger.len(), 5);
+    assert_eq!(
+        logger.to_string(),
+        format!("Logger {{ {} }}", logger.stats())
+    );
+
+    // Formatting while this thread holds the queue's lock doesn't deadlock.
+    let guard = logger.lock();
+    assert_eq!(logger.to_string(), "Logger { locked }");
+    drop(guard);
+    drop(logger);
+
+    // A poisoned queue is reported rather than panicking. This uses an
+    // isolated logger so the global queue stays usable.
+    let logger = Logger::start_isolated();
+    log::info!("poison");
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| logger.retain(|_| panic!("boom"))));
+    assert_eq!(logger.to_string(), "Logger { poisoned }");
+}
@@ -0,0 +1,44 @@
+use log::Level;
+use logtest::{Logger, RecordMatcher};
+
+#[test]
+fn matcher() {
+    let mut logger = Logger::start();
+    log::info!(target: "my_crate::db", peer = "a"; "connected");
+    log::warn!(target: "my_crate::http", peer = "b", status = 504; "request timeout");
+    log::info!(target: "hyper", "idle");
+
+    // Zero constraints match everything.
+    let everything = RecordMatcher::new();
+    assert!(logger.all(&everything));
+    assert!(logger.any(&everything));
+
+    // A single constraint.
+    let warnings = RecordMatcher::new().level(Level::Warn);
+    assert!(logger.any(&warnings));
+    assert!(!logger.all(&warnings));
+    assert!(logger.any(&RecordMatcher::new().args_eq("idle")));
+    assert!(logger.any(&RecordMatcher::new().target("hyper")));
+    assert!(!logger.any(&RecordMatcher::new().has_kv("missing")));
+
+    // Several constraints.
+    let timeout = RecordMatcher::new()
+        .level(Level::Warn)
+        .args_contains("timeout")
+        .target_starts_with("my_crate")
+        .has_kv("peer")
+        .kv_eq("status", 504);
+    let record = logger.records().remove(1);
+    assert!(record.matches(&timeout));
+    assert!(!record.matches(&timeout.clone().kv_eq("peer", "a")));
+    assert!(!logger.all(&RecordMatcher::new().target_starts_with("my_crate")));
+
+    let record = logger.pop_matching(&timeout).unwrap();
+    assert_eq!(record.args(), "request timeout");
+    assert!(logger.pop_matching(&timeout).is_none());
+    assert_eq!(logger.pop_front().unwrap().args(), "connected");
+    assert_eq!(logger.pop_front().unwrap().args(), "idle");
+
+    assert!(logger.all(&warnings));
+    assert!(!logger.any(&everything));
+}
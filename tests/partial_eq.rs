@@ -0,0 +1,22 @@
+use log::Level;
+use logtest::Logger;
+
+#[test]
+fn partial_eq() {
+    let logger = Logger::start();
+    log::info!(target: "other", peer = "a"; "hello");
+    log::warn!("disk nearly full");
+
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record, "hello");
+    assert_eq!("hello", record);
+    assert_ne!(record, "world");
+    assert_eq!(record, (Level::Info, "hello"));
+    assert_eq!((Level::Info, "hello"), record);
+    assert_ne!(record, (Level::Warn, "hello"));
+
+    assert_eq!(
+        logger.pop_front().unwrap(),
+        (Level::Warn, "disk nearly full")
+    );
+}
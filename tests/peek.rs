@@ -0,0 +1,30 @@
+use log::Level;
+use logtest::Logger;
+
+#[test]
+fn peek() {
+    let logger = Logger::start();
+    assert_eq!(logger.peek(), None);
+
+    log::warn!("careful");
+    let peeked = logger.peek().unwrap();
+    assert_eq!(peeked.level(), Level::Warn);
+    assert_eq!(logger.len(), 1);
+
+    let args = logger.peek_with(|record| record.unwrap().args().to_owned());
+    assert_eq!(args, "careful");
+    assert_eq!(logger.len(), 1);
+
+    let popped = logger.pop_front().unwrap();
+    assert_eq!(popped.args(), "careful");
+    assert_eq!(popped, peeked);
+    assert_eq!(popped.seq(), peeked.seq());
+    assert!(logger.peek_with(|record| record.is_none()));
+
+    assert_eq!(logger.peek_back(), None);
+    log::info!("first");
+    log::info!("second");
+    assert_eq!(logger.peek_back().unwrap().args(), "second");
+    assert_eq!(logger.peek().unwrap().args(), "first");
+    assert_eq!(logger.len(), 2);
+}
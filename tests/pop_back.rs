@@ -0,0 +1,27 @@
+use logtest::Logger;
+
+#[test]
+fn pop_back() {
+    let mut logger = Logger::start();
+    log::info!("first");
+    log::info!("second");
+    log::info!("third");
+
+    assert_eq!(logger.pop_back().unwrap().args(), "third");
+    assert_eq!(logger.next_back().unwrap().args(), "second");
+    assert_eq!(logger.pop_front().unwrap().args(), "first");
+    assert_eq!(logger.pop_back(), None);
+
+    for i in 0..5 {
+        log::info!("{}", i);
+    }
+    let newest_first: Vec<_> = logger.iter().rev().map(|r| r.args().to_owned()).collect();
+    assert_eq!(newest_first, ["4", "3", "2", "1", "0"]);
+    let newest: Vec<_> = logger.by_ref().rev().take(1).collect();
+    assert_eq!(newest[0].args(), "4");
+    assert_eq!(logger.pop_front().unwrap().args(), "0");
+    assert_eq!(logger.pop_back().unwrap().args(), "3");
+    assert_eq!(logger.pop_front().unwrap().args(), "1");
+    assert_eq!(logger.pop_back().unwrap().args(), "2");
+    assert_eq!(logger.pop_front(), None);
+}
@@ -0,0 +1,24 @@
+use logtest::Logger;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn pop_blocking() {
+    let logger = Logger::start();
+
+    log::info!("queued");
+    let start = Instant::now();
+    let record = logger.pop_blocking(Duration::from_secs(5)).unwrap();
+    assert_eq!(record.args(), "queued");
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    let worker = thread::spawn(|| {
+        thread::sleep(Duration::from_millis(50));
+        log::info!("later");
+    });
+    let record = logger.pop_blocking(Duration::from_secs(5)).unwrap();
+    assert_eq!(record.args(), "later");
+    worker.join().unwrap();
+
+    assert!(logger.pop_blocking(Duration::from_millis(10)).is_none());
+}
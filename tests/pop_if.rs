@@ -0,0 +1,17 @@
+use logtest::Logger;
+
+#[test]
+fn pop_if() {
+    let logger = Logger::start();
+    assert!(logger.pop_if(|_| true).is_none());
+
+    log::info!("benign");
+    log::warn!("important");
+
+    assert!(logger.pop_if(|r| r.args() == "important").is_none());
+    assert_eq!(logger.len(), 2);
+
+    let record = logger.pop_if(|r| r.args() == "benign").unwrap();
+    assert_eq!(record.args(), "benign");
+    assert_eq!(logger.pop_front().unwrap().args(), "important");
+}
@@ -0,0 +1,31 @@
+use logtest::Logger;
+
+#[test]
+fn pop_n() {
+    let logger = Logger::start();
+    for i in 0..5 {
+        log::info!("message {}", i);
+    }
+
+    let records = logger.pop_n(2);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].args(), "message 0");
+    assert_eq!(records[1].args(), "message 1");
+
+    let records = logger.pop_n(3);
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[2].args(), "message 4");
+    assert!(logger.is_empty());
+
+    log::info!("last");
+    let records = logger.pop_n(10);
+    assert_eq!(records.len(), 1);
+    assert!(logger.pop_n(1).is_empty());
+
+    log::info!("one");
+    log::info!("two");
+    let records = logger.pop_all();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].args(), "one");
+    assert!(logger.is_empty());
+}
@@ -0,0 +1,17 @@
+use logtest::Logger;
+
+#[test]
+fn position() {
+    let mut logger = Logger::start();
+    log::info!("first");
+    log::info!("second");
+    log::info!("third");
+
+    let first = logger.position(|r| r.args() == "first").unwrap();
+    let third = logger.position(|r| r.args() == "third").unwrap();
+    assert_eq!(first, 0);
+    assert_eq!(third, 2);
+    assert!(first < third);
+    assert_eq!(logger.position(|r| r.args() == "missing"), None);
+    assert_eq!(logger.len(), 3);
+}
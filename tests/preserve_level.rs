@@ -0,0 +1,17 @@
+use log::LevelFilter;
+use logtest::Logger;
+
+#[test]
+fn preserve_level() {
+    log::set_max_level(LevelFilter::Warn);
+    let logger = Logger::start_preserving_level();
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+
+    log::info!("ignored");
+    log::warn!("captured");
+    assert_eq!(logger.pop_front().unwrap().args(), "captured");
+    assert!(logger.is_empty());
+
+    drop(logger);
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+}
@@ -0,0 +1,35 @@
+use log::Level;
+use logtest::{Logger, Record};
+
+#[test]
+fn record_builder() {
+    let logger = Logger::start();
+
+    kv_log_macro::warn!("disk nearly full", { free_mb: 12, mount: "/" });
+    let record = logger.pop_front().unwrap();
+    let expected = Record::builder()
+        .args("disk nearly full")
+        .level(Level::Warn)
+        .target("record_builder")
+        .key_value("free_mb", 12)
+        .key_value("mount", "/")
+        .build();
+    assert_eq!(record, expected);
+    assert_ne!(record, Record::builder().args("disk nearly full").build());
+
+    // Partial comparisons only look at the fields that were set.
+    assert!(Record::builder().args("disk nearly full").matches(&record));
+    assert!(Record::builder()
+        .level(Level::Warn)
+        .key_value("free_mb", 12)
+        .key_value("mount", "/")
+        .matches(&record));
+    assert!(!Record::builder().level(Level::Error).matches(&record));
+    assert!(!Record::builder().key_value("free_mb", 12).matches(&record));
+
+    let empty = Record::builder().build();
+    assert_eq!(empty.args(), "");
+    assert_eq!(empty.level(), Level::Info);
+    assert_eq!(empty.target(), "");
+    assert!(empty.key_values().is_empty());
+}
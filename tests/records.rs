@@ -0,0 +1,20 @@
+use logtest::Logger;
+
+#[test]
+fn records() {
+    let logger = Logger::start();
+    log::info!("hello");
+    log::warn!("world");
+
+    let first = logger.records();
+    assert_eq!(first, logger.records());
+    log::info!("later");
+    let second = logger.records();
+
+    let args = |records: &[logtest::Record]| -> Vec<String> {
+        records.iter().map(|r| r.args().to_owned()).collect()
+    };
+    assert_eq!(args(&first), ["hello", "world"]);
+    assert_eq!(args(&second), ["hello", "world", "later"]);
+    assert_eq!(logger.len(), 3);
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "regex")]
+
+use logtest::Logger;
+use regex::Regex;
+
+#[test]
+fn regex() {
+    let mut logger = Logger::start();
+    log::info!("listening on port 8080");
+    log::info!("user 42 connected");
+    log::info!("user 7 connected");
+
+    let re = Regex::new(r"user \d+ connected").unwrap();
+    let record = logger.pop_matching(&re).unwrap();
+    assert_eq!(record.args(), "user 42 connected");
+    assert!(record.args_matches(&re));
+    assert!(logger.any(&re));
+    assert!(!logger.all(&re));
+
+    assert_eq!(logger.pop_front().unwrap().args(), "listening on port 8080");
+    assert!(logger.all(&re));
+    assert!(logger
+        .pop_matching(&Regex::new("disconnected").unwrap())
+        .is_none());
+}
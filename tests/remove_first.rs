@@ -0,0 +1,25 @@
+use logtest::Logger;
+
+#[test]
+fn remove_first() {
+    let logger = Logger::start();
+    log::info!("first");
+    log::warn!("match 1");
+    log::info!("second");
+    log::warn!("match 2");
+
+    let record = logger
+        .remove_first(|r| r.args().starts_with("match"))
+        .unwrap();
+    assert_eq!(record.args(), "match 1");
+    let record = logger
+        .remove_first(|r| r.args().starts_with("match"))
+        .unwrap();
+    assert_eq!(record.args(), "match 2");
+    assert!(logger
+        .remove_first(|r| r.args().starts_with("match"))
+        .is_none());
+
+    assert_eq!(logger.pop_front().unwrap().args(), "first");
+    assert_eq!(logger.pop_front().unwrap().args(), "second");
+}
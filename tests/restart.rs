@@ -0,0 +1,15 @@
+use logtest::Logger;
+
+#[test]
+fn restart() {
+    let first = Logger::start();
+    log::info!("hello");
+
+    // Restarting clears the queue, but the handles share it.
+    let second = Logger::try_start().unwrap();
+    assert!(first.is_empty());
+    log::info!("world");
+
+    assert_eq!(first.pop_front().unwrap().args(), "world");
+    assert!(second.is_empty());
+}
@@ -0,0 +1,27 @@
+use log::Level;
+use logtest::Logger;
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn retain() {
+    let logger = Logger::start();
+    log::info!(target: "hyper", "polling");
+    log::info!(target: "my_crate", "one");
+    log::warn!(target: "mio", "slow");
+    log::warn!(target: "my_crate::db", "two");
+
+    logger.retain(|r| r.target().starts_with("my_crate"));
+    assert_eq!(logger.len(), 2);
+    assert_eq!(logger.peek().unwrap().args(), "one");
+
+    logger.retain(|r| r.level() == Level::Warn);
+    assert_eq!(logger.len(), 1);
+    assert_eq!(logger.pop_front().unwrap().args(), "two");
+
+    // A panicking predicate doesn't break the queue.
+    log::info!("kept");
+    let result = panic::catch_unwind(AssertUnwindSafe(|| logger.retain(|_| panic!("boom"))));
+    assert!(result.is_err());
+    log::info!("after");
+    assert_eq!(logger.len(), 2);
+}
@@ -0,0 +1,32 @@
+use logtest::Logger;
+use std::thread;
+
+#[test]
+fn seq() {
+    let mut logger = Logger::start();
+
+    let workers: Vec<_> = ["a", "b"]
+        .iter()
+        .map(|target| {
+            thread::spawn(move || {
+                for i in 0..5 {
+                    log::info!(target: target, "message {}", i);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let records: Vec<_> = logger.by_ref().collect();
+    assert_eq!(records.len(), 10);
+    assert!(records.windows(2).all(|w| w[0].seq() < w[1].seq()));
+
+    let (a, b): (Vec<_>, Vec<_>) = records.iter().partition(|r| r.target() == "a");
+    let mut merged: Vec<_> = a.into_iter().chain(b).collect();
+    merged.sort_by_key(|r| r.seq());
+    let merged: Vec<_> = merged.into_iter().map(|r| r.seq()).collect();
+    let expected: Vec<_> = records.iter().map(|r| r.seq()).collect();
+    assert_eq!(merged, expected);
+}
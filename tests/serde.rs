@@ -0,0 +1,57 @@
+#![cfg(feature = "serde")]
+
+use log::Level;
+use logtest::{Logger, Record, Value};
+
+#[test]
+fn serde() {
+    let logger = Logger::start();
+    kv_log_macro::warn!("disk nearly full", { mount: "/", free_mb: 12, ok: false });
+    log::trace!(ratio = 0.5, big = u64::MAX, neg = -3; "numbers");
+
+    let record = logger.pop_front().unwrap();
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(
+        json,
+        r#"{"args":"disk nearly full","level":"WARN","target":"serde","key_values":{"mount":"/","free_mb":12,"ok":false}}"#
+    );
+    let parsed: Record = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, record);
+    assert_eq!(parsed.level(), Level::Warn);
+    assert_eq!(parsed.key_values(), record.key_values());
+
+    let record = logger.pop_front().unwrap();
+    let parsed: Record = serde_json::from_str(&serde_json::to_string(&record).unwrap()).unwrap();
+    assert_eq!(parsed, record);
+    assert_eq!(parsed.level(), Level::Trace);
+    assert_eq!(
+        parsed.key_values(),
+        vec![
+            ("ratio".to_owned(), Value::F64(0.5)),
+            ("big".to_owned(), Value::U64(u64::MAX)),
+            ("neg".to_owned(), Value::I64(-3)),
+        ]
+    );
+
+    log::info!(list:? = vec![1, 2], name = "[1, 2]"; "debug");
+    let record = logger.pop_front().unwrap();
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(
+        json,
+        r#"{"args":"debug","level":"INFO","target":"serde","key_values":{"list":{"debug":"[1, 2]"},"name":"[1, 2]"}}"#
+    );
+    let parsed: Record = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, record);
+    assert_eq!(
+        parsed.key_value("list"),
+        Some(&Value::Debug("[1, 2]".to_owned()))
+    );
+    assert_eq!(
+        parsed.key_value("name"),
+        Some(&Value::String("[1, 2]".to_owned()))
+    );
+
+    let err =
+        serde_json::from_str::<Record>(r#"{"args":"","level":"LOUD","target":"","key_values":{}}"#);
+    assert!(err.is_err());
+}
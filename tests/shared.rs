@@ -0,0 +1,35 @@
+use logtest::Logger;
+use std::thread;
+use std::time::Duration;
+
+fn count(logger: &Logger) -> usize {
+    logger.len()
+}
+
+#[test]
+fn shared() {
+    let logger = Logger::start();
+
+    let popped = thread::scope(|s| {
+        let consumer = s.spawn(|| {
+            let mut popped = Vec::new();
+            for _ in 0..100 {
+                let record = logger
+                    .pop_blocking(Duration::from_secs(5))
+                    .expect("record not delivered");
+                popped.push(record.args().to_owned());
+            }
+            popped
+        });
+        s.spawn(|| {
+            for i in 0..100 {
+                log::info!("message {}", i);
+            }
+        });
+        consumer.join().unwrap()
+    });
+
+    let expected: Vec<_> = (0..100).map(|i| format!("message {}", i)).collect();
+    assert_eq!(popped, expected);
+    assert_eq!(count(&logger), 0);
+}
@@ -0,0 +1,22 @@
+use logtest::Logger;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn sink() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink_seen = seen.clone();
+    let logger = Logger::start_with_sink(move |record| {
+        sink_seen.lock().unwrap().push(record.args().to_owned());
+    });
+
+    log::info!("hello");
+    log::warn!("world");
+    assert_eq!(*seen.lock().unwrap(), ["hello", "world"]);
+    assert_eq!(logger.pop_front().unwrap().args(), "hello");
+    assert_eq!(logger.pop_front().unwrap().args(), "world");
+
+    // Starting a logger without a sink stops calling it.
+    let _logger = Logger::start();
+    log::info!("ignored");
+    assert_eq!(seen.lock().unwrap().len(), 2);
+}
@@ -0,0 +1,18 @@
+use logtest::Logger;
+
+#[test]
+fn snapshot() {
+    let logger = Logger::start();
+    assert_eq!(logger.snapshot(), "");
+
+    log::info!(target: "app", zeta = 1, alpha = "a", mid = true; "started");
+    log::error!(target: "app::db", "failed");
+    log::debug!(target: "app", list:? = vec![1, 2]; "details");
+
+    let expected = "\
+INFO app: started [alpha=\"a\", mid=true, zeta=1]
+ERROR app::db: failed
+DEBUG app: details [list=[1, 2]]";
+    assert_eq!(logger.snapshot(), expected);
+    assert!(logger.is_empty());
+}
@@ -0,0 +1,31 @@
+use log::{Metadata, Record};
+use logtest::Logger;
+use std::panic;
+
+struct OtherLogger;
+
+impl log::Log for OtherLogger {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+    fn log(&self, _record: &Record<'_>) {}
+    fn flush(&self) {}
+}
+
+#[test]
+fn start_error() {
+    log::set_logger(&OtherLogger).unwrap();
+
+    let err = Logger::try_start().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "a different global logger has already been installed"
+    );
+
+    let err = panic::catch_unwind(Logger::start).unwrap_err();
+    assert_eq!(
+        err.downcast::<String>().unwrap().as_str(),
+        "failed to start the test logger: a different global logger has already been \
+         installed; use `try_start` to handle this"
+    );
+}
@@ -0,0 +1,28 @@
+use logtest::Logger;
+use std::sync::{Mutex, MutexGuard};
+
+// The queue is global, so these tests take turns instead of running in
+// parallel.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[test]
+fn start_per_test_first() {
+    let _serial = serial();
+    let logger = Logger::start();
+    log::info!("first");
+    assert_eq!(logger.pop_front().unwrap().args(), "first");
+    log::info!("left behind");
+}
+
+#[test]
+fn start_per_test_second() {
+    let _serial = serial();
+    let logger = Logger::start();
+    log::info!("second");
+    assert_eq!(logger.pop_front().unwrap().args(), "second");
+    log::info!("left behind");
+}
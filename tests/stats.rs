@@ -0,0 +1,31 @@
+use log::Level;
+use logtest::Logger;
+
+#[test]
+fn stats() {
+    let logger = Logger::start();
+    let stats = logger.stats();
+    assert_eq!(stats.total(), 0);
+    assert_eq!(stats.level(Level::Info), 0);
+    assert!(stats.targets().is_empty());
+    assert_eq!(stats.to_string(), "0 records");
+
+    log::info!(target: "a", "one");
+    log::warn!(target: "b", "two");
+    log::info!(target: "a", "three");
+    log::error!(target: "c", "four");
+
+    let stats = logger.stats();
+    assert_eq!(stats.total(), 4);
+    assert_eq!(stats.level(Level::Info), 2);
+    assert_eq!(stats.level(Level::Warn), 1);
+    assert_eq!(stats.level(Level::Debug), 0);
+    assert_eq!(stats.target("a"), 2);
+    assert_eq!(stats.target("missing"), 0);
+    assert_eq!(stats.levels().len(), 3);
+    assert_eq!(
+        stats.to_string(),
+        "4 records; levels: ERROR=1 WARN=1 INFO=2; targets: a=2 b=1 c=1"
+    );
+    assert_eq!(logger.len(), 4);
+}
@@ -0,0 +1,18 @@
+use logtest::Logger;
+
+#[test]
+fn target() {
+    let logger = Logger::start_with_target("my_crate");
+    log::info!(target: "hyper::proto", "ignored");
+    log::info!(target: "my_crate::db", "captured");
+    assert_eq!(logger.len(), 1);
+    assert_eq!(logger.pop_front().unwrap().target(), "my_crate::db");
+
+    let logger = Logger::builder().target("a").target("b").start();
+    log::info!(target: "a", "first");
+    log::info!(target: "mio", "ignored");
+    log::info!(target: "b", "second");
+    assert_eq!(logger.pop_front().unwrap().args(), "first");
+    assert_eq!(logger.pop_front().unwrap().args(), "second");
+    assert!(logger.is_empty());
+}
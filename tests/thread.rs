@@ -0,0 +1,28 @@
+use logtest::Logger;
+use std::thread;
+
+#[test]
+fn thread() {
+    let logger = Logger::start();
+
+    let handle = thread::Builder::new()
+        .name("uploader".to_owned())
+        .spawn(|| log::warn!("upload failed"))
+        .unwrap();
+    let uploader = handle.thread().id();
+    handle.join().unwrap();
+
+    let handle = thread::spawn(|| log::info!("unnamed"));
+    let unnamed = handle.thread().id();
+    handle.join().unwrap();
+
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(msg.args(), "upload failed");
+    assert_eq!(msg.thread_id(), uploader);
+    assert_eq!(msg.thread_name(), Some("uploader"));
+
+    let msg = logger.pop_front().unwrap();
+    assert_eq!(msg.args(), "unnamed");
+    assert_eq!(msg.thread_id(), unnamed);
+    assert_eq!(msg.thread_name(), None);
+}
@@ -0,0 +1,24 @@
+use logtest::Logger;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn timing() {
+    let logger = Logger::start();
+    log::info!("first");
+    log::info!("second");
+
+    let first = logger.pop_front().unwrap();
+    let second = logger.pop_front().unwrap();
+    assert!(first.captured_at() <= second.captured_at());
+    assert!(second.captured_at() - first.captured_at() < Duration::from_millis(100));
+    assert!(first.timestamp() <= second.timestamp());
+    assert!(first.elapsed() <= second.elapsed());
+
+    log::info!("retrying");
+    thread::sleep(Duration::from_millis(50));
+    log::info!("giving up");
+    let retrying = logger.pop_front().unwrap();
+    let giving_up = logger.pop_front().unwrap();
+    assert!(giving_up.elapsed() - retrying.elapsed() >= Duration::from_millis(50));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "tracing")]
+
+use logtest::{Logger, Record};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn tracing() {
+    let logger = Logger::builder().tracing().start();
+    tracing::info!(color = "blue", "hello");
+    log::info!("from log");
+    tracing::debug!(count = 3, ok = true, "world {}", 1);
+
+    let expected = Record::builder()
+        .args("hello")
+        .target(module_path!())
+        .key_value("color", "blue");
+    assert_eq!(logger.pop_front().unwrap(), expected.build());
+    assert_eq!(logger.pop_front().unwrap().args(), "from log");
+
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record.args(), "world 1");
+    assert_eq!(record.level(), log::Level::Debug);
+    assert_eq!(record.key_value("count").unwrap().as_i64(), Some(3));
+    assert_eq!(record.key_value("ok").unwrap().as_bool(), Some(true));
+    assert!(logger.is_empty());
+    drop(logger);
+
+    // Starting again reuses the installed subscriber.
+    let logger = Logger::builder().tracing().start();
+    tracing::warn!("again");
+    assert_eq!(logger.pop_front().unwrap().args(), "again");
+    drop(logger);
+
+    // Nothing is captured once every handle is dropped.
+    let seen = Arc::new(AtomicUsize::new(0));
+    let counter = seen.clone();
+    let logger = Logger::builder()
+        .tracing()
+        .sink(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .start();
+    let handle = logger.clone();
+    drop(logger);
+    tracing::info!("still captured");
+    assert_eq!(seen.load(Ordering::SeqCst), 1);
+    drop(handle);
+    tracing::info!("dropped");
+    assert_eq!(seen.load(Ordering::SeqCst), 1);
+
+    let logger = Logger::builder().tracing().start();
+    assert!(logger.is_empty());
+}
@@ -0,0 +1,24 @@
+use logtest::Logger;
+
+#[test]
+fn truncate() {
+    let logger = Logger::start();
+    logger.truncate(2);
+    logger.keep_last(2);
+    assert!(logger.is_empty());
+
+    for i in 0..5 {
+        log::info!("message {}", i);
+    }
+    logger.truncate(10);
+    assert_eq!(logger.len(), 5);
+    logger.truncate(3);
+    assert_eq!(logger.len(), 3);
+    assert_eq!(logger.peek().unwrap().args(), "message 0");
+
+    logger.keep_last(10);
+    assert_eq!(logger.len(), 3);
+    logger.keep_last(1);
+    assert_eq!(logger.len(), 1);
+    assert_eq!(logger.pop_front().unwrap().args(), "message 2");
+}
@@ -0,0 +1,27 @@
+use logtest::Logger;
+
+#[test]
+fn truncate_args() {
+    let logger = Logger::builder().max_args_len(1024).start();
+
+    log::trace!("{}", "x".repeat(10 * 1024));
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record.args().len(), 1024);
+    assert!(record.truncated());
+
+    log::info!("short");
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record.args(), "short");
+    assert!(!record.truncated());
+
+    // "é" is two bytes, so cutting at three bytes backs off to a boundary.
+    let logger = Logger::builder().max_args_len(3).start();
+    log::info!("ééé");
+    let record = logger.pop_front().unwrap();
+    assert_eq!(record.args(), "é");
+    assert!(record.truncated());
+
+    let logger = Logger::start();
+    log::info!("{}", "x".repeat(10 * 1024));
+    assert!(!logger.pop_front().unwrap().truncated());
+}
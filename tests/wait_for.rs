@@ -0,0 +1,37 @@
+use logtest::Logger;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn wait_for() {
+    let logger = Logger::start();
+
+    let worker = thread::spawn(|| {
+        log::info!("working");
+        thread::sleep(Duration::from_millis(50));
+        log::info!("done");
+    });
+    let start = Instant::now();
+    let record = logger
+        .wait_for(|r| r.args() == "done", Duration::from_millis(500))
+        .unwrap();
+    assert_eq!(record.args(), "done");
+    assert!(start.elapsed() < Duration::from_millis(500));
+    worker.join().unwrap();
+    assert_eq!(logger.pop_front().unwrap().args(), "working");
+
+    let record = logger.wait_for(|r| r.args() == "never", Duration::from_millis(10));
+    assert!(record.is_none());
+
+    // `Duration::MAX` waits without a deadline.
+    log::info!("queued");
+    let record = logger.pop_blocking(Duration::MAX).unwrap();
+    assert_eq!(record.args(), "queued");
+    let worker = thread::spawn(|| {
+        thread::sleep(Duration::from_millis(20));
+        log::info!("eventually");
+    });
+    let record = logger.wait_for(|r| r.args() == "eventually", Duration::MAX);
+    assert_eq!(record.unwrap().args(), "eventually");
+    worker.join().unwrap();
+}
@@ -0,0 +1,35 @@
+use logtest::Logger;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn wait_for_count() {
+    let logger = Logger::start();
+    let handles: Vec<_> = (0..3)
+        .map(|i| {
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10 * i));
+                log::info!("task {}", i);
+            })
+        })
+        .collect();
+
+    assert!(logger.wait_for_count(3, Duration::from_secs(5)));
+    assert_eq!(logger.len(), 3);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // Times out when the count is never reached.
+    assert!(!logger.wait_for_count(4, Duration::from_millis(10)));
+    assert!(logger.wait_for_count(0, Duration::ZERO));
+
+    // `Duration::MAX` waits without a deadline.
+    assert!(logger.wait_for_count(3, Duration::MAX));
+    let worker = thread::spawn(|| {
+        thread::sleep(Duration::from_millis(20));
+        log::info!("fourth");
+    });
+    assert!(logger.wait_for_count(4, Duration::MAX));
+    worker.join().unwrap();
+}